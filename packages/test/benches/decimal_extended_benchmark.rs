@@ -1,5 +1,6 @@
 use criterion::{BatchSize, Criterion, black_box, criterion_group, criterion_main};
 use tests::{
+    batch_lookups,
     generated_uniprops::uniprops::{Category, get_digit_value},
     without_0x38,
 };
@@ -30,6 +31,12 @@ const TEST_TEXT: &str =  "\
 
 const DIGITS: &str = "1234567890";
 
+const ASCII_TEXT: &str = "\
+        w0-0234.34fwe09832489239r80)99fdsf w0-0234.34fwe09832489239r80)99fdsf \
+        w0-0234.34fwe09832489239r80)99fdsf w0-0234.34fwe09832489239r80)99fdsf \
+        w0-0234.34fwe09832489239r80)99fdsf w0-0234.34fwe09832489239r80)99fdsf\
+    ";
+
 fn benchmark_categories(c: &mut Criterion) {
     c.bench_function("Categories", |b| {
         b.iter_batched(
@@ -47,6 +54,46 @@ fn benchmark_categories(c: &mut Criterion) {
         );
     });
 
+    // Whole buffers at once, reported per buffer against a loop of scalar lookups
+    for (name, text) in [("mixed", TEST_TEXT), ("ASCII", ASCII_TEXT)] {
+        let chars: Vec<char> = text.chars().collect();
+        let mut categories = vec![None; chars.len()];
+        c.bench_function(&format!("Categories (batch, {name})"), |b| {
+            b.iter(|| {
+                black_box(batch_lookups::uniprops::Category::classify_str(
+                    black_box(text),
+                    &mut categories,
+                ))
+            });
+        });
+        c.bench_function(&format!("Categories (scalar loop, {name})"), |b| {
+            b.iter(|| {
+                for (dst, c) in categories.iter_mut().zip(black_box(text).chars()) {
+                    *dst = batch_lookups::uniprops::Category::from_char(c);
+                }
+                black_box(&categories);
+            });
+        });
+
+        let mut values = vec![None; chars.len()];
+        c.bench_function(&format!("Digits (batch, {name})"), |b| {
+            b.iter(|| {
+                black_box(batch_lookups::uniprops::get_digit_values(
+                    black_box(&chars),
+                    &mut values,
+                ))
+            });
+        });
+        c.bench_function(&format!("Digits (scalar loop, {name})"), |b| {
+            b.iter(|| {
+                for (dst, &c) in values.iter_mut().zip(black_box(&chars)) {
+                    *dst = batch_lookups::uniprops::get_digit_value(c);
+                }
+                black_box(&values);
+            });
+        });
+    }
+
     c.bench_function("Is numeric", |b| {
         b.iter_batched(
             || TEST_TEXT.chars().cycle(),
@@ -0,0 +1,67 @@
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use tests::{bsearch, eytzinger, generated_uniprops};
+
+/// Mixed scripts, so that the lookups hit ranges all over the tables.
+const TEST_TEXT: &str = "\
+        Hello, world! Grüße aus Köln. Ελληνικά κείμενα. Русский текст. \
+        עברית וערבית العربية. हिन्दी पाठ. ภาษาไทย. 日本語のテキスト、한국어 텍스트. \
+        𝐌𝐚𝐭𝐡 𝟏𝟐𝟑 🙂🚀 ᚠᚢᚦ 𐌰𐌱𐌲 ꦗꦮ ";
+
+/// Pseudo-random codepoints from the BMP and the SMP, so that the branches of the searches
+/// cannot be predicted.
+fn random_chars() -> Vec<char> {
+    let mut state = 0x2545_F491_u32;
+    (0..4096)
+        .filter_map(|_| {
+            // xorshift32
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            char::from_u32(state % 0x20000)
+        })
+        .collect()
+}
+
+fn benchmark_lookup_strategies(c: &mut Criterion) {
+    let inputs = [
+        ("text", TEST_TEXT.chars().collect::<Vec<_>>()),
+        ("random", random_chars()),
+    ];
+
+    for (input, chars) in &inputs {
+        let mut group = c.benchmark_group(format!("Category::from_char ({input})"));
+
+        // The iterators live outside of `iter`, so every iteration looks up the next character
+        let mut iter = chars.iter().cycle();
+        group.bench_function("BSearch", |b| {
+            b.iter(|| {
+                black_box(bsearch::uniprops::Category::from_char(
+                    *iter.next().unwrap(),
+                ))
+            })
+        });
+
+        let mut iter = chars.iter().cycle();
+        group.bench_function("Eytzinger", |b| {
+            b.iter(|| {
+                black_box(eytzinger::uniprops::Category::from_char(
+                    *iter.next().unwrap(),
+                ))
+            })
+        });
+
+        let mut iter = chars.iter().cycle();
+        group.bench_function("Trie", |b| {
+            b.iter(|| {
+                black_box(generated_uniprops::uniprops::Category::from_char(
+                    *iter.next().unwrap(),
+                ))
+            })
+        });
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, benchmark_lookup_strategies);
+criterion_main!(benches);
@@ -1,4 +1,4 @@
-use uniprops_gen::{LookupStrategy, UnipropsBuilder};
+use uniprops_gen::{CategoryFastPath, LookupObjective, LookupStrategy, UnipropsBuilder};
 
 fn main() {
     UnipropsBuilder::new().build();
@@ -24,6 +24,280 @@ fn main() {
         .out_file("without_0x38.rs")
         .build();
 
+    UnipropsBuilder::new()
+        .with_age(true)
+        .out_file("age.rs")
+        .build();
+
+    UnipropsBuilder::new()
+        .with_age(true)
+        .with_digits(false)
+        .with_lookup_strategy(LookupStrategy::BSearch)
+        .assigned_since(6, 0)
+        .out_file("assigned_since_6_0.rs")
+        .build();
+
+    UnipropsBuilder::new()
+        .with_categories(false)
+        .with_digits(false)
+        .with_indic_syllabic_category(true)
+        .with_indic_positional_category(true)
+        .out_file("indic.rs")
+        .build();
+
+    UnipropsBuilder::new()
+        .with_categories(false)
+        .with_digits(false)
+        .with_joining_type(true)
+        .with_joining_group(true)
+        .out_file("joining.rs")
+        .build();
+
+    UnipropsBuilder::new()
+        .with_categories(false)
+        .with_digits(false)
+        .with_vertical_orientation(true)
+        .with_lookup_strategy(LookupStrategy::BSearch)
+        .out_file("vertical_orientation.rs")
+        .build();
+
+    UnipropsBuilder::new()
+        .with_categories(false)
+        .with_digits(false)
+        .with_unihan("data/unihan")
+        .out_file("unihan.rs")
+        .build();
+
+    UnipropsBuilder::new()
+        .with_categories(false)
+        .with_digits(false)
+        .with_confusables(true)
+        .out_file("confusables.rs")
+        .build();
+
+    UnipropsBuilder::new()
+        // CYRILLIC SMALL LETTER A
+        .filter(|r| r.code_point != 0x0430)
+        .with_categories(false)
+        .with_digits(false)
+        .with_confusables(true)
+        .out_file("confusables_without_0x430.rs")
+        .build();
+
+    UnipropsBuilder::new()
+        .with_categories(false)
+        .with_digits(false)
+        .with_identifier_security(true)
+        .out_file("identifier_security.rs")
+        .build();
+
+    UnipropsBuilder::new()
+        .with_categories(false)
+        .with_digits(false)
+        .with_identifiers(true)
+        .with_lookup_strategy(LookupStrategy::BSearch)
+        .out_file("identifiers.rs")
+        .build();
+
+    UnipropsBuilder::new()
+        .with_categories(false)
+        .with_digits(false)
+        .with_collation(true)
+        .out_file("collation.rs")
+        .build();
+
+    // Splits the unified ideograph ranges, down to the single U+9FCC of Unicode 6.1
+    UnipropsBuilder::new()
+        .with_categories(false)
+        .with_digits(false)
+        .with_collation(true)
+        .assigned_since(6, 1)
+        .out_file("collation_6_1.rs")
+        .build();
+
+    UnipropsBuilder::new()
+        .with_categories(false)
+        .with_digits(false)
+        .with_variation_sequences(true)
+        .out_file("variation_sequences.rs")
+        .build();
+
+    UnipropsBuilder::new()
+        .with_categories(false)
+        .with_digits(false)
+        .with_emoji_sequences(true)
+        .with_flags(true)
+        .out_file("emoji.rs")
+        .build();
+
+    UnipropsBuilder::new()
+        .with_categories(false)
+        .with_digits(false)
+        .with_case_orbits(true)
+        .out_file("case_orbits.rs")
+        .build();
+
+    UnipropsBuilder::new()
+        .with_categories(false)
+        .with_digits(false)
+        .with_case_properties(true)
+        .out_file("case_properties.rs")
+        .build();
+
+    UnipropsBuilder::new()
+        .with_categories(false)
+        .with_digits(false)
+        .with_nfkc_casefold(true)
+        .out_file("nfkc_casefold.rs")
+        .build();
+
+    UnipropsBuilder::new()
+        .with_age(true)
+        .with_lookup_strategy(LookupStrategy::Trie3 {
+            shift1: 10,
+            shift2: 5,
+        })
+        .out_file("trie3.rs")
+        .build();
+
+    UnipropsBuilder::new()
+        .with_categories(false)
+        .with_digits(false)
+        .with_identifiers(true)
+        .out_file("identifiers_bitset.rs")
+        .build();
+
+    UnipropsBuilder::new()
+        .with_age(true)
+        .with_packed_blocks(true)
+        .out_file("packed_blocks.rs")
+        .build();
+
+    UnipropsBuilder::new()
+        .with_categories(false)
+        .with_digits(false)
+        .with_unihan("data/unihan")
+        .with_packed_blocks(true)
+        .out_file("unihan_packed_blocks.rs")
+        .build();
+
+    UnipropsBuilder::new()
+        .with_age(true)
+        .with_packed_blocks(true)
+        .with_lookup_strategy(LookupStrategy::Trie3 {
+            shift1: 10,
+            shift2: 5,
+        })
+        .out_file("packed_blocks_trie3.rs")
+        .build();
+
+    // More than 256 blocks, so the blocks overlap and the indices store their starts
+    for (packed, out_file) in [
+        (false, "trie_shift_4.rs"),
+        (true, "packed_blocks_shift_4.rs"),
+    ] {
+        UnipropsBuilder::new()
+            .with_age(true)
+            .with_packed_blocks(packed)
+            .with_lookup_strategy(LookupStrategy::Trie { shift: 4 })
+            .out_file(out_file)
+            .build();
+    }
+
+    UnipropsBuilder::new()
+        .with_age(true)
+        .with_identifiers(true)
+        .with_lookup_strategy(LookupStrategy::Auto {
+            objective: LookupObjective::Size,
+        })
+        .out_file("auto_size.rs")
+        .build();
+
+    UnipropsBuilder::new()
+        .with_age(true)
+        .with_identifiers(true)
+        .with_lookup_strategy(LookupStrategy::Auto {
+            objective: LookupObjective::Speed,
+        })
+        .out_file("auto_speed.rs")
+        .build();
+
+    UnipropsBuilder::new()
+        .filter(|r| r.general_category == "Nd")
+        .with_lookup_strategy(LookupStrategy::Auto {
+            objective: LookupObjective::Size,
+        })
+        .out_file("auto_filtered_digits.rs")
+        .build();
+
+    UnipropsBuilder::new()
+        .with_category_fast_path(CategoryFastPath::Latin1)
+        .out_file("latin1_fast_path.rs")
+        .build();
+
+    UnipropsBuilder::new()
+        .filter(|r| r.general_category == "Nd" || r.code_point < 0x41)
+        .with_category_fast_path(CategoryFastPath::Ascii)
+        .with_lookup_strategy(LookupStrategy::BSearch)
+        .out_file("ascii_fast_path_bsearch.rs")
+        .build();
+
+    UnipropsBuilder::new()
+        .with_age(true)
+        .with_identifiers(true)
+        .with_lookup_strategy(LookupStrategy::Eytzinger)
+        .out_file("eytzinger.rs")
+        .build();
+
+    UnipropsBuilder::new()
+        .with_lookup_strategy(LookupStrategy::BSearch)
+        .out_file("bsearch.rs")
+        .build();
+
+    UnipropsBuilder::new()
+        .filter(|r| r.general_category == "Nd")
+        .with_lookup_strategy(LookupStrategy::PerfectHash)
+        .out_file("perfect_hash_nd.rs")
+        .build();
+
+    // Too many categorized codepoints for the perfect hash, so it falls back to a binary search
+    UnipropsBuilder::new()
+        .with_lookup_strategy(LookupStrategy::PerfectHash)
+        .out_file("perfect_hash_full.rs")
+        .build();
+
+    // A few hundred scattered codepoints
+    for (strategy, out_file) in [
+        (LookupStrategy::PerfectHash, "perfect_hash_scattered.rs"),
+        (LookupStrategy::BSearch, "bsearch_scattered.rs"),
+    ] {
+        UnipropsBuilder::new()
+            .filter(|r| r.code_point % 61 == 7)
+            .with_digits(false)
+            .with_lookup_strategy(strategy)
+            .out_file(out_file)
+            .build();
+    }
+
+    UnipropsBuilder::new()
+        .with_batch_lookups(true)
+        .out_file("batch_lookups.rs")
+        .build();
+
+    UnipropsBuilder::new()
+        .filter(|r| r.code_point != 0x38)
+        .with_batch_lookups(true)
+        .out_file("batch_lookups_without_0x38.rs")
+        .build();
+
+    for (packed, out_file) in [(false, "utf8_lookup.rs"), (true, "utf8_lookup_packed.rs")] {
+        UnipropsBuilder::new()
+            .with_utf8_lookup(true)
+            .with_packed_blocks(packed)
+            .out_file(out_file)
+            .build();
+    }
+
     UnipropsBuilder::new()
         // 0 and 8
         .filter(|r| [0x30, 0x38].contains(&r.code_point))
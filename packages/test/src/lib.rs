@@ -21,6 +21,162 @@ pub mod bsearch_nd {
     include!(concat!(env!("OUT_DIR"), "/filtered_digits_bsearch.rs"));
 }
 
+pub mod age {
+    include!(concat!(env!("OUT_DIR"), "/age.rs"));
+}
+
+pub mod assigned_since_6_0 {
+    include!(concat!(env!("OUT_DIR"), "/assigned_since_6_0.rs"));
+}
+
+pub mod indic {
+    include!(concat!(env!("OUT_DIR"), "/indic.rs"));
+}
+
+pub mod joining {
+    include!(concat!(env!("OUT_DIR"), "/joining.rs"));
+}
+
+pub mod vertical_orientation {
+    include!(concat!(env!("OUT_DIR"), "/vertical_orientation.rs"));
+}
+
+pub mod unihan {
+    include!(concat!(env!("OUT_DIR"), "/unihan.rs"));
+}
+
+pub mod confusables {
+    include!(concat!(env!("OUT_DIR"), "/confusables.rs"));
+}
+
+pub mod confusables_without_0x430 {
+    include!(concat!(env!("OUT_DIR"), "/confusables_without_0x430.rs"));
+}
+
+pub mod identifier_security {
+    include!(concat!(env!("OUT_DIR"), "/identifier_security.rs"));
+}
+
+pub mod identifiers {
+    include!(concat!(env!("OUT_DIR"), "/identifiers.rs"));
+}
+
+pub mod collation {
+    include!(concat!(env!("OUT_DIR"), "/collation.rs"));
+}
+
+pub mod variation_sequences {
+    include!(concat!(env!("OUT_DIR"), "/variation_sequences.rs"));
+}
+
+pub mod emoji {
+    include!(concat!(env!("OUT_DIR"), "/emoji.rs"));
+}
+
+pub mod case_orbits {
+    include!(concat!(env!("OUT_DIR"), "/case_orbits.rs"));
+}
+
+pub mod case_properties {
+    include!(concat!(env!("OUT_DIR"), "/case_properties.rs"));
+}
+
+pub mod nfkc_casefold {
+    include!(concat!(env!("OUT_DIR"), "/nfkc_casefold.rs"));
+}
+
+pub mod trie3 {
+    include!(concat!(env!("OUT_DIR"), "/trie3.rs"));
+}
+
+pub mod identifiers_bitset {
+    include!(concat!(env!("OUT_DIR"), "/identifiers_bitset.rs"));
+}
+
+pub mod packed_blocks {
+    include!(concat!(env!("OUT_DIR"), "/packed_blocks.rs"));
+}
+
+pub mod unihan_packed_blocks {
+    include!(concat!(env!("OUT_DIR"), "/unihan_packed_blocks.rs"));
+}
+
+pub mod packed_blocks_trie3 {
+    include!(concat!(env!("OUT_DIR"), "/packed_blocks_trie3.rs"));
+}
+
+pub mod auto_size {
+    include!(concat!(env!("OUT_DIR"), "/auto_size.rs"));
+}
+
+pub mod auto_speed {
+    include!(concat!(env!("OUT_DIR"), "/auto_speed.rs"));
+}
+
+pub mod auto_filtered_digits {
+    include!(concat!(env!("OUT_DIR"), "/auto_filtered_digits.rs"));
+}
+
+pub mod latin1_fast_path {
+    include!(concat!(env!("OUT_DIR"), "/latin1_fast_path.rs"));
+}
+
+pub mod ascii_fast_path_bsearch {
+    include!(concat!(env!("OUT_DIR"), "/ascii_fast_path_bsearch.rs"));
+}
+
+pub mod eytzinger {
+    include!(concat!(env!("OUT_DIR"), "/eytzinger.rs"));
+}
+
+pub mod bsearch {
+    include!(concat!(env!("OUT_DIR"), "/bsearch.rs"));
+}
+
+pub mod perfect_hash_nd {
+    include!(concat!(env!("OUT_DIR"), "/perfect_hash_nd.rs"));
+}
+
+pub mod perfect_hash_full {
+    include!(concat!(env!("OUT_DIR"), "/perfect_hash_full.rs"));
+}
+
+pub mod perfect_hash_scattered {
+    include!(concat!(env!("OUT_DIR"), "/perfect_hash_scattered.rs"));
+}
+
+pub mod bsearch_scattered {
+    include!(concat!(env!("OUT_DIR"), "/bsearch_scattered.rs"));
+}
+
+pub mod utf8_lookup {
+    include!(concat!(env!("OUT_DIR"), "/utf8_lookup.rs"));
+}
+
+pub mod utf8_lookup_packed {
+    include!(concat!(env!("OUT_DIR"), "/utf8_lookup_packed.rs"));
+}
+
+pub mod batch_lookups {
+    include!(concat!(env!("OUT_DIR"), "/batch_lookups.rs"));
+}
+
+pub mod batch_lookups_without_0x38 {
+    include!(concat!(env!("OUT_DIR"), "/batch_lookups_without_0x38.rs"));
+}
+
+pub mod trie_shift_4 {
+    include!(concat!(env!("OUT_DIR"), "/trie_shift_4.rs"));
+}
+
+pub mod packed_blocks_shift_4 {
+    include!(concat!(env!("OUT_DIR"), "/packed_blocks_shift_4.rs"));
+}
+
+pub mod collation_6_1 {
+    include!(concat!(env!("OUT_DIR"), "/collation_6_1.rs"));
+}
+
 #[cfg(test)]
 mod tests {
     use std::char;
@@ -257,4 +413,1003 @@ mod tests {
         assert_eq!(TEST_ARRAY_OF_DEC_VALUES[0], 0);
         assert_eq!(TEST_ARRAY_OF_DEC_VALUES[1], 8);
     }
+
+    #[test]
+    fn test_age() {
+        use age::uniprops::{UnicodeVersion, age};
+
+        assert_eq!(age('A'), Some(UnicodeVersion { major: 1, minor: 1 }));
+        assert_eq!(age('\u{20AC}'), Some(UnicodeVersion { major: 2, minor: 1 })); // '€'
+        assert_eq!(age('😊'), Some(UnicodeVersion { major: 6, minor: 0 }));
+        assert_eq!(age('🦀'), Some(UnicodeVersion { major: 8, minor: 0 }));
+        assert_eq!(age('\u{0378}'), None); // Unassigned
+
+        assert!(age('🦀') > age('😊'));
+    }
+
+    #[test]
+    fn test_assigned_since() {
+        use assigned_since_6_0::uniprops::{Category, UnicodeVersion, age};
+
+        assert_eq!(Category::from_char('A'), Some(Category::Lu));
+        assert_eq!(Category::from_char('😊'), Some(Category::So));
+        assert_eq!(Category::from_char('🦀'), None); // Unicode 8.0
+
+        assert_eq!(age('😊'), Some(UnicodeVersion { major: 6, minor: 0 }));
+        assert_eq!(age('🦀'), None);
+
+        // `First`/`Last` ranges are clipped instead of collapsing to their first codepoint
+        assert_eq!(Category::from_char('字'), Some(Category::Lo)); // U+5B57
+        assert_eq!(Category::from_char('\u{9FCB}'), Some(Category::Lo)); // Unicode 5.2
+        assert_eq!(Category::from_char('\u{9FCC}'), None); // Unicode 6.1
+        assert_eq!(Category::from_char('한'), Some(Category::Lo)); // U+D55C
+        assert_eq!(Category::from_char('\u{2A6D6}'), Some(Category::Lo)); // Unicode 3.1
+        assert_eq!(Category::from_char('\u{2A6D7}'), None); // Unicode 13.0
+    }
+
+    #[test]
+    fn test_indic_categories() {
+        use indic::uniprops::{IndicPositionalCategory, IndicSyllabicCategory};
+
+        assert_eq!(
+            IndicSyllabicCategory::from_char('\u{0915}'), // DEVANAGARI LETTER KA
+            IndicSyllabicCategory::Consonant
+        );
+        assert_eq!(
+            IndicSyllabicCategory::from_char('\u{093F}'), // DEVANAGARI VOWEL SIGN I
+            IndicSyllabicCategory::VowelDependent
+        );
+        assert_eq!(
+            IndicSyllabicCategory::from_char('\u{094D}'), // DEVANAGARI SIGN VIRAMA
+            IndicSyllabicCategory::Virama
+        );
+        assert_eq!(
+            IndicSyllabicCategory::from_char('A'),
+            IndicSyllabicCategory::Other
+        );
+
+        assert_eq!(
+            IndicPositionalCategory::from_char('\u{093F}'),
+            IndicPositionalCategory::Left
+        );
+        assert_eq!(
+            IndicPositionalCategory::from_char('\u{0940}'),
+            IndicPositionalCategory::Right
+        );
+        assert_eq!(
+            IndicPositionalCategory::from_char('\u{094D}'),
+            IndicPositionalCategory::Bottom
+        );
+        assert_eq!(
+            IndicPositionalCategory::from_char('A'),
+            IndicPositionalCategory::NA
+        );
+    }
+
+    #[test]
+    fn test_joining_type_and_group() {
+        use joining::uniprops::{JoiningGroup, JoiningType};
+
+        assert_eq!(JoiningType::from_char('\u{0628}'), JoiningType::DualJoining); // ARABIC LETTER BEH
+        assert_eq!(
+            JoiningType::from_char('\u{0627}'),
+            JoiningType::RightJoining
+        ); // ARABIC LETTER ALEF
+        assert_eq!(JoiningType::from_char('\u{200D}'), JoiningType::JoinCausing); // ZWJ
+        assert_eq!(JoiningType::from_char('\u{064B}'), JoiningType::Transparent); // ARABIC FATHATAN
+        assert_eq!(JoiningType::from_char('\u{0301}'), JoiningType::Transparent); // Derived from Mn
+        assert_eq!(JoiningType::from_char('A'), JoiningType::NonJoining);
+
+        assert_eq!(JoiningGroup::from_char('\u{0628}'), JoiningGroup::Beh);
+        assert_eq!(JoiningGroup::from_char('\u{0710}'), JoiningGroup::Alaph); // SYRIAC LETTER ALAPH
+        assert_eq!(
+            JoiningGroup::from_char('\u{06C3}'),
+            JoiningGroup::TehMarbutaGoal
+        );
+        assert_eq!(
+            JoiningGroup::from_char('\u{200D}'),
+            JoiningGroup::NoJoiningGroup
+        );
+        assert_eq!(JoiningGroup::from_char('A'), JoiningGroup::NoJoiningGroup);
+    }
+
+    #[test]
+    fn test_vertical_orientation() {
+        use vertical_orientation::uniprops::VerticalOrientation;
+
+        assert_eq!(VerticalOrientation::from_char('A'), VerticalOrientation::R);
+        assert_eq!(VerticalOrientation::from_char('字'), VerticalOrientation::U);
+        assert_eq!(VerticalOrientation::from_char('あ'), VerticalOrientation::U);
+        assert_eq!(
+            VerticalOrientation::from_char('\u{3001}'),
+            VerticalOrientation::Tu
+        ); // '、'
+        assert_eq!(
+            VerticalOrientation::from_char('\u{3041}'),
+            VerticalOrientation::Tu
+        ); // 'ぁ'
+        assert_eq!(
+            VerticalOrientation::from_char('\u{30FC}'),
+            VerticalOrientation::Tr
+        ); // 'ー'
+        assert_eq!(
+            VerticalOrientation::from_char('\u{FF08}'),
+            VerticalOrientation::Tr
+        ); // '（'
+
+        // Unassigned codepoints take the default of their range
+        assert_eq!(
+            VerticalOrientation::from_char('\u{0378}'),
+            VerticalOrientation::R
+        );
+        assert_eq!(
+            VerticalOrientation::from_char('\u{3FFFD}'),
+            VerticalOrientation::U
+        );
+    }
+
+    #[test]
+    fn test_unihan() {
+        use unihan::uniprops::{
+            RadicalStroke, k_accounting_numeric, k_mandarin, k_primary_numeric, k_rs_unicode,
+            k_total_strokes,
+        };
+
+        assert_eq!(
+            k_rs_unicode('字'),
+            Some(RadicalStroke {
+                radical: 39,
+                simplified: false,
+                strokes: 3
+            })
+        );
+        assert_eq!(
+            k_rs_unicode('门'),
+            Some(RadicalStroke {
+                radical: 169,
+                simplified: true,
+                strokes: 0
+            })
+        );
+        assert_eq!(k_rs_unicode('A'), None);
+
+        assert_eq!(k_total_strokes('一'), Some(1));
+        assert_eq!(k_total_strokes('壹'), Some(12));
+        assert_eq!(k_total_strokes('A'), None);
+
+        // Unihan entries newer than the bundled DerivedAge.txt are kept while no `assigned_since` is set.
+        assert_eq!(
+            k_rs_unicode('\u{31350}'),
+            Some(RadicalStroke {
+                radical: 1,
+                simplified: false,
+                strokes: 4
+            })
+        );
+        assert_eq!(k_total_strokes('\u{31350}'), Some(5));
+
+        let mut by_strokes = ['壹', '三', '一', '二'];
+        by_strokes.sort_by_key(|&c| k_total_strokes(c));
+        assert_eq!(by_strokes, ['一', '二', '三', '壹']);
+
+        assert_eq!(k_mandarin('字'), Some("zì"));
+        assert_eq!(k_mandarin('一'), k_mandarin('壹'));
+        assert_eq!(k_mandarin('A'), None);
+
+        assert_eq!(k_primary_numeric('三'), Some(3));
+        assert_eq!(k_primary_numeric('兆'), Some(1_000_000_000_000));
+        assert_eq!(k_primary_numeric('壹'), None);
+        assert_eq!(k_accounting_numeric('壹'), Some(1));
+    }
+
+    #[test]
+    fn test_nfd() {
+        use confusables::uniprops::{canonical_combining_class, nfd};
+
+        assert_eq!(nfd("abc"), "abc");
+        assert_eq!(nfd("\u{00E9}"), "e\u{0301}"); // 'é'
+        assert_eq!(nfd("\u{01D6}"), "u\u{0308}\u{0304}"); // 'ǖ', recursive decomposition
+        assert_eq!(nfd("\u{D55C}"), "\u{1112}\u{1161}\u{11AB}"); // '한'
+        assert_eq!(nfd("\u{AC00}"), "\u{1100}\u{1161}"); // '가', no trailing consonant
+
+        // Canonical ordering: dot below (220) goes before acute (230)
+        assert_eq!(nfd("a\u{0301}\u{0323}"), "a\u{0323}\u{0301}");
+        assert_eq!(nfd("\u{1E0B}\u{0323}"), "d\u{0323}\u{0307}"); // 'ḋ' + dot below
+
+        assert_eq!(canonical_combining_class('a'), 0);
+        assert_eq!(canonical_combining_class('\u{0301}'), 230);
+        assert_eq!(canonical_combining_class('\u{0323}'), 220);
+    }
+
+    #[test]
+    fn test_confusables() {
+        use confusables::uniprops::{confusable_prototype, is_confusable, skeleton};
+
+        assert_eq!(confusable_prototype('\u{0441}'), Some(&['c'][..])); // CYRILLIC SMALL LETTER ES
+        assert_eq!(confusable_prototype('c'), None);
+
+        assert_eq!(
+            skeleton("paypal"),
+            skeleton("\u{0440}\u{0430}\u{0443}\u{0440}\u{0430}l")
+        );
+        assert!(is_confusable(
+            "paypal",
+            "\u{0440}\u{0430}\u{0443}\u{0440}\u{0430}l"
+        ));
+        assert!(is_confusable("paypal", "paypa1"));
+        assert!(!is_confusable("paypal", "paypat"));
+
+        // SOFT HYPHEN and ZERO WIDTH JOINER are default ignorable
+        assert_eq!(skeleton("pay\u{00AD}pal\u{200D}"), skeleton("paypal"));
+    }
+
+    #[test]
+    fn test_confusables_respect_filter() {
+        use confusables_without_0x430::uniprops::{confusable_prototype, is_confusable, nfd};
+
+        assert_eq!(confusable_prototype('\u{0430}'), None);
+        assert!(!is_confusable("a", "\u{0430}"));
+        assert!(is_confusable("c", "\u{0441}"));
+
+        // Normalization ignores the filter
+        assert_eq!(nfd("\u{00E9}"), "e\u{0301}");
+    }
+
+    #[test]
+    fn test_identifier_security() {
+        use identifier_security::uniprops::{
+            IdentifierStatus, IdentifierType, RestrictionLevel, Script, identifier_status,
+            identifier_type, restriction_level, script_extensions,
+        };
+
+        assert_eq!(Script::from_char('a'), Script::Latin);
+        assert_eq!(Script::from_char('\u{0430}'), Script::Cyrillic);
+        assert_eq!(Script::from_char('\u{10FFFF}'), Script::Unknown);
+        assert_eq!(script_extensions('a'), &[Script::Latin]);
+        assert_eq!(
+            script_extensions('\u{0484}'),
+            &[Script::Cyrillic, Script::Glagolitic]
+        );
+        assert_eq!(
+            script_extensions('\u{30FC}'),
+            &[Script::Hiragana, Script::Katakana]
+        ); // 'ー'
+
+        assert_eq!(identifier_status('a'), IdentifierStatus::Allowed);
+        assert_eq!(identifier_status('!'), IdentifierStatus::Restricted);
+        assert_eq!(identifier_type('a'), &[IdentifierType::Recommended]);
+        assert_eq!(identifier_type('!'), &[IdentifierType::NotXID]);
+        assert_eq!(
+            identifier_type('\u{018D}'),
+            &[IdentifierType::Technical, IdentifierType::Obsolete]
+        );
+        assert_eq!(identifier_type('\u{FFFF}'), &[IdentifierType::NotCharacter]);
+
+        assert_eq!(restriction_level("paypal"), RestrictionLevel::AsciiOnly);
+        // U+002D is in the identifier profile
+        assert_eq!(restriction_level("foo-bar"), RestrictionLevel::AsciiOnly);
+        // ASCII, but outside the identifier profile
+        for s in ["a b", "a\0", "x\u{7F}"] {
+            assert_eq!(
+                restriction_level(s),
+                RestrictionLevel::Unrestricted,
+                "{s:?}"
+            );
+        }
+        assert_eq!(
+            restriction_level("caf\u{00E9}"),
+            RestrictionLevel::SingleScript
+        );
+        assert_eq!(
+            restriction_level("\u{043F}\u{0440}\u{0438}\u{0432}\u{0435}\u{0442}"),
+            RestrictionLevel::SingleScript
+        );
+        // Kanji, hiragana and katakana resolve to Japanese
+        assert_eq!(
+            restriction_level("\u{65E5}\u{672C}\u{3054}\u{30AB}"),
+            RestrictionLevel::SingleScript
+        );
+        assert_eq!(
+            restriction_level("abc\u{65E5}\u{672C}"),
+            RestrictionLevel::HighlyRestrictive
+        );
+        assert_eq!(
+            restriction_level("abc\u{05D0}\u{00E9}"),
+            RestrictionLevel::ModeratelyRestrictive
+        );
+        // Latin + Cyrillic is the classic homograph attack
+        assert_eq!(
+            restriction_level("p\u{0430}yp\u{0430}l\u{00E9}"),
+            RestrictionLevel::MinimallyRestrictive
+        );
+        assert_eq!(
+            restriction_level("caf\u{00E9}\u{2764}"),
+            RestrictionLevel::Unrestricted
+        );
+    }
+
+    #[test]
+    fn test_identifiers() {
+        use identifiers::uniprops::{
+            IdentifierProfile, is_identifier, is_xid_continue, is_xid_start,
+        };
+
+        assert!(is_xid_start('a'));
+        assert!(!is_xid_start('1'));
+        assert!(is_xid_continue('1'));
+        assert!(is_xid_continue('_'));
+        assert!(!is_xid_start('_'));
+
+        assert!(is_identifier("foo_bar1"));
+        assert!(is_identifier(
+            "\u{043F}\u{0440}\u{0438}\u{0432}\u{0435}\u{0442}"
+        ));
+        assert!(is_identifier("\u{5909}\u{6570}"));
+        assert!(!is_identifier(""));
+        assert!(!is_identifier("1abc"));
+        assert!(!is_identifier("_abc"));
+        assert!(!is_identifier("foo-bar"));
+        assert!(!is_identifier("a\u{200C}b"));
+
+        const JS: IdentifierProfile = IdentifierProfile {
+            extra_start: &['$', '_'],
+            ..IdentifierProfile::DEFAULT
+        };
+        assert!(JS.is_identifier("$foo"));
+        assert!(JS.is_identifier("_foo$"));
+        assert!(!JS.is_identifier("foo-bar"));
+
+        const KEBAB: IdentifierProfile = IdentifierProfile {
+            medial: &['-'],
+            ..IdentifierProfile::DEFAULT
+        };
+        assert!(KEBAB.is_identifier("foo-bar"));
+        assert!(!KEBAB.is_identifier("-foo"));
+        assert!(!KEBAB.is_identifier("foo-"));
+        assert!(!KEBAB.is_identifier("a--b"));
+
+        const CATALAN: IdentifierProfile = IdentifierProfile {
+            medial: &['-', '\u{00B7}'],
+            ..IdentifierProfile::DEFAULT
+        };
+        assert!(CATALAN.is_identifier("col\u{00B7}lecci\u{00F3}-a"));
+        assert!(!CATALAN.is_identifier("a\u{00B7}\u{00B7}b"));
+        assert!(!CATALAN.is_identifier("a-\u{00B7}b"));
+
+        const JOINERS: IdentifierProfile = IdentifierProfile {
+            allow_joiners: true,
+            ..IdentifierProfile::DEFAULT
+        };
+        // ZWNJ after DEVANAGARI SIGN VIRAMA (rule A2)
+        assert!(JOINERS.is_identifier("\u{0915}\u{094D}\u{200C}\u{0937}"));
+        // ZWNJ between ARABIC LETTER BEH (dual-joining) and ARABIC LETTER REH (right-joining) (rule A1)
+        assert!(JOINERS.is_identifier("\u{0628}\u{200C}\u{0631}"));
+        // REH does not join to the left
+        assert!(!JOINERS.is_identifier("\u{0631}\u{200C}\u{0628}"));
+        assert!(!JOINERS.is_identifier("a\u{200D}b"));
+    }
+
+    #[test]
+    fn test_collation() {
+        use collation::uniprops::{CollationStrength, compare, compare_with_strength, sort_key};
+        use std::cmp::Ordering;
+
+        assert_eq!(compare("a", "B"), Ordering::Less);
+        assert_eq!(compare("a", "A"), Ordering::Less);
+        assert_eq!(compare("A", "\u{00E1}"), Ordering::Less); // 'á'
+        assert_eq!(compare("\u{00E4}", "b"), Ordering::Less); // 'ä'
+        assert_eq!(compare("abc", "abc"), Ordering::Equal);
+        // Precomposed and decomposed forms are equal
+        assert_eq!(compare("\u{00E9}", "e\u{0301}"), Ordering::Equal);
+
+        assert_eq!(
+            compare_with_strength("r\u{00E9}sum\u{00E9}", "resume", CollationStrength::Primary),
+            Ordering::Equal
+        );
+        assert_eq!(
+            compare_with_strength("Resume", "resume", CollationStrength::Secondary),
+            Ordering::Equal
+        );
+        assert_eq!(compare("r\u{00E9}sum\u{00E9}", "resume"), Ordering::Greater);
+
+        // CYRILLIC SMALL LETTER SHORT I is a contraction with its own primary weight
+        assert_eq!(
+            compare_with_strength("\u{0439}", "\u{0438}", CollationStrength::Primary),
+            Ordering::Greater
+        );
+
+        // Implicit weights of unified ideographs follow codepoint order
+        assert_eq!(compare("\u{4E00}", "\u{4E01}"), Ordering::Less);
+        assert_eq!(compare("\u{4E00}", "\u{20000}"), Ordering::Less);
+        assert_eq!(compare("z", "\u{4E00}"), Ordering::Less);
+
+        let mut names = vec!["\u{00C9}clair", "apple", "Zebra", "eclair", "banana"];
+        names.sort_by(|a, b| compare(a, b));
+        assert_eq!(
+            names,
+            ["apple", "banana", "eclair", "\u{00C9}clair", "Zebra"]
+        );
+
+        assert_eq!(sort_key("a"), sort_key("a"));
+        assert!(sort_key("a") < sort_key("b"));
+    }
+
+    #[test]
+    fn test_variation_sequences() {
+        use variation_sequences::uniprops::{is_valid_variation_sequence, variation_description};
+
+        assert_eq!(variation_description('#', '\u{FE0F}'), Some("emoji style"));
+        assert_eq!(variation_description('#', '\u{FE0E}'), Some("text style"));
+        assert_eq!(
+            variation_description('\u{8C48}', '\u{FE00}'),
+            Some("CJK COMPATIBILITY IDEOGRAPH-F900")
+        );
+        assert!(is_valid_variation_sequence('\u{2764}', '\u{FE0F}')); // '❤'
+        assert!(!is_valid_variation_sequence('a', '\u{FE0F}'));
+        assert!(!is_valid_variation_sequence('\u{8C48}', '\u{FE0F}'));
+    }
+
+    #[test]
+    fn test_emoji_sequences() {
+        use emoji::uniprops::{EmojiSequenceKind, emoji_sequences};
+
+        let text = "Hi \u{1F44B}\u{1F3FD}! \u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467} in \u{1F1E9}\u{1F1EA} #\u{FE0F}\u{20E3} #1 \u{2764}\u{FE0F}\u{1F600}";
+        let found = emoji_sequences(text)
+            .map(|m| (m.text, m.name, m.kind))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            found,
+            [
+                (
+                    "\u{1F44B}\u{1F3FD}",
+                    "waving hand: medium skin tone",
+                    EmojiSequenceKind::RGIEmojiModifierSequence
+                ),
+                (
+                    "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}",
+                    "family: man, woman, girl",
+                    EmojiSequenceKind::RGIEmojiZWJSequence
+                ),
+                (
+                    "\u{1F1E9}\u{1F1EA}",
+                    "flag: Germany",
+                    EmojiSequenceKind::RGIEmojiFlagSequence
+                ),
+                (
+                    "#\u{FE0F}\u{20E3}",
+                    "keycap: #",
+                    EmojiSequenceKind::EmojiKeycapSequence
+                ),
+                (
+                    "\u{2764}\u{FE0F}",
+                    "red heart",
+                    EmojiSequenceKind::BasicEmoji
+                ),
+                ("\u{1F600}", "grinning face", EmojiSequenceKind::BasicEmoji),
+            ]
+        );
+
+        assert_eq!(emoji_sequences("plain text #1").count(), 0);
+        // A lone text-default character is not fully qualified
+        assert_eq!(emoji_sequences("\u{2764}").count(), 0);
+    }
+
+    #[test]
+    fn test_flags() {
+        use emoji::uniprops::{flag_country_code, is_regional_indicator};
+
+        assert!(is_regional_indicator('\u{1F1E6}'));
+        assert!(is_regional_indicator('\u{1F1FF}'));
+        assert!(!is_regional_indicator('A'));
+
+        assert_eq!(flag_country_code("\u{1F1E9}\u{1F1EA}"), Some(*b"DE"));
+        assert_eq!(flag_country_code("\u{1F1FA}\u{1F1F8}"), Some(*b"US"));
+        assert_eq!(flag_country_code("\u{1F1EA}\u{1F1FA}"), Some(*b"EU"));
+        // Valid regional indicators, but not an RGI flag
+        assert_eq!(flag_country_code("\u{1F1E6}\u{1F1E6}"), None);
+        assert_eq!(flag_country_code("\u{1F1E9}"), None);
+        assert_eq!(flag_country_code("\u{1F1E9}\u{1F1EA}\u{1F1E9}"), None);
+        assert_eq!(flag_country_code("DE"), None);
+    }
+
+    #[test]
+    fn test_case_orbits() {
+        use case_orbits::uniprops::case_orbit;
+
+        assert_eq!(case_orbit('k'), &['K', 'k', '\u{212A}']); // KELVIN SIGN
+        assert_eq!(case_orbit('\u{212A}'), case_orbit('K'));
+        assert_eq!(case_orbit('s'), &['S', 's', '\u{017F}']); // LATIN SMALL LETTER LONG S
+        // Sigma has two lowercase forms
+        assert_eq!(
+            case_orbit('\u{03C2}'),
+            &['\u{03A3}', '\u{03C2}', '\u{03C3}']
+        );
+        // DŽ, Dž, dž
+        assert_eq!(
+            case_orbit('\u{01C5}'),
+            &['\u{01C4}', '\u{01C5}', '\u{01C6}']
+        );
+        assert_eq!(case_orbit('\u{00DF}'), &['\u{00DF}', '\u{1E9E}']); // 'ß', 'ẞ'
+        // The Turkic dotted and dotless i only fold together with `T` foldings
+        assert_eq!(case_orbit('i'), &['I', 'i']);
+        assert!(case_orbit('\u{0130}').is_empty()); // 'İ'
+        assert!(case_orbit('\u{0131}').is_empty()); // 'ı'
+        assert!(case_orbit('1').is_empty());
+        assert!(case_orbit('\u{4E00}').is_empty());
+
+        for c in ('\0'..=char::MAX).filter(|c| !case_orbit(*c).is_empty()) {
+            assert!(case_orbit(c).contains(&c));
+            for &other in case_orbit(c) {
+                assert_eq!(case_orbit(other), case_orbit(c));
+            }
+        }
+    }
+
+    #[test]
+    fn test_case_properties() {
+        use case_properties::uniprops::*;
+
+        assert!(is_cased('a') && is_cased('\u{1C5}') && !is_cased('1'));
+        assert!(is_case_ignorable('\'') && is_case_ignorable('\u{301}') && !is_case_ignorable('a'));
+
+        assert!(changes_when_lowercased('A') && !changes_when_lowercased('a'));
+        assert!(changes_when_uppercased('a') && changes_when_uppercased('ß'));
+        assert!(!changes_when_uppercased('A') && !changes_when_uppercased('1'));
+        // Titlecase digraph: dž -> Dž
+        assert!(changes_when_titlecased('\u{1C6}') && !changes_when_titlecased('\u{1C5}'));
+        assert!(changes_when_casefolded('ß') && !changes_when_casefolded('s'));
+        assert!(changes_when_casemapped('a') && !changes_when_casemapped('-'));
+
+        // Compatibility characters fold to their decomposition, default ignorables to nothing
+        assert!(changes_when_nfkc_casefolded('\u{FB01}') && changes_when_nfkc_casefolded('\u{AD}'));
+        assert!(changes_when_nfkc_casefolded('A') && !changes_when_nfkc_casefolded('a'));
+        assert!(!"hello world".chars().any(changes_when_lowercased));
+    }
+
+    #[test]
+    fn test_nfkc_casefold() {
+        use nfkc_casefold::uniprops::nfkc_casefold;
+
+        assert_eq!(nfkc_casefold("Hello"), "hello");
+        assert_eq!(nfkc_casefold("Straße"), "strasse");
+        assert_eq!(nfkc_casefold("STRASSE"), nfkc_casefold("ｓｔｒａｓｓｅ"));
+        assert_eq!(nfkc_casefold("\u{1E9E}"), "ss");
+        assert_eq!(nfkc_casefold("\u{FB01}le"), "file");
+        assert_eq!(nfkc_casefold("Ⅸ"), "ix");
+        // Default ignorables are removed
+        assert_eq!(nfkc_casefold("a\u{AD}b\u{200B}"), "ab");
+
+        // The result is in NFC
+        assert_eq!(nfkc_casefold("E\u{301}"), "\u{E9}");
+        assert_eq!(nfkc_casefold("\u{C9}"), "\u{E9}");
+        assert_eq!(nfkc_casefold("\u{1C5}"), "d\u{17E}");
+        assert_eq!(nfkc_casefold("a\u{323}\u{302}"), "\u{1EAD}");
+        assert_eq!(nfkc_casefold("\u{1100}\u{1161}\u{11A8}"), "\u{AC01}");
+        assert_eq!(nfkc_casefold("한국어"), "한국어");
+        // Composition exclusions stay decomposed
+        assert_eq!(nfkc_casefold("\u{915}\u{93C}"), "\u{915}\u{93C}");
+    }
+
+    #[test]
+    fn test_trie3_matches_trie() {
+        for cp in 0..=0x10FFFF {
+            if let Some(c) = char::from_u32(cp) {
+                assert_eq!(
+                    trie3::uniprops::Category::from_char(c).map(|c| c as u8),
+                    generated_uniprops::uniprops::Category::from_char(c).map(|c| c as u8),
+                    "U+{cp:04X}"
+                );
+                assert_eq!(
+                    trie3::uniprops::get_digit_value(c),
+                    generated_uniprops::uniprops::get_digit_value(c)
+                );
+                assert_eq!(
+                    trie3::uniprops::age(c).map(|v| (v.major, v.minor)),
+                    age::uniprops::age(c).map(|v| (v.major, v.minor)),
+                    "U+{cp:04X}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_bitset_matches_bsearch() {
+        use identifiers::uniprops as bsearch;
+        use identifiers_bitset::uniprops as bitset;
+
+        for cp in 0..=0x10FFFF {
+            if let Some(c) = char::from_u32(cp) {
+                assert_eq!(
+                    bitset::is_xid_start(c),
+                    bsearch::is_xid_start(c),
+                    "U+{cp:04X}"
+                );
+                assert_eq!(
+                    bitset::is_xid_continue(c),
+                    bsearch::is_xid_continue(c),
+                    "U+{cp:04X}"
+                );
+            }
+        }
+        assert!(bitset::is_identifier("foo_1") && !bitset::is_identifier("1foo"));
+    }
+
+    #[test]
+    fn test_packed_blocks_match_unpacked() {
+        for cp in 0..=0x10FFFF {
+            if let Some(c) = char::from_u32(cp) {
+                let category = age::uniprops::Category::from_char(c).map(|c| c as u8);
+                let age = age::uniprops::age(c).map(|v| (v.major, v.minor));
+
+                assert_eq!(
+                    packed_blocks::uniprops::Category::from_char(c).map(|c| c as u8),
+                    category,
+                    "U+{cp:04X}"
+                );
+                assert_eq!(
+                    packed_blocks::uniprops::age(c).map(|v| (v.major, v.minor)),
+                    age,
+                    "U+{cp:04X}"
+                );
+                assert_eq!(
+                    packed_blocks_trie3::uniprops::Category::from_char(c).map(|c| c as u8),
+                    category,
+                    "U+{cp:04X}"
+                );
+                assert_eq!(
+                    packed_blocks_trie3::uniprops::age(c).map(|v| (v.major, v.minor)),
+                    age,
+                    "U+{cp:04X}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_unihan_packed_blocks_match_unpacked() {
+        use unihan_packed_blocks::uniprops as packed;
+
+        for c in ['字', '门', '一', '壹', '三', '兆', '\u{31350}', 'A'] {
+            assert_eq!(
+                packed::k_rs_unicode(c).map(|rs| (rs.radical, rs.simplified, rs.strokes)),
+                unihan::uniprops::k_rs_unicode(c).map(|rs| (rs.radical, rs.simplified, rs.strokes))
+            );
+            assert_eq!(
+                packed::k_total_strokes(c),
+                unihan::uniprops::k_total_strokes(c)
+            );
+            assert_eq!(packed::k_mandarin(c), unihan::uniprops::k_mandarin(c));
+            assert_eq!(
+                packed::k_primary_numeric(c),
+                unihan::uniprops::k_primary_numeric(c)
+            );
+            assert_eq!(
+                packed::k_accounting_numeric(c),
+                unihan::uniprops::k_accounting_numeric(c)
+            );
+        }
+    }
+
+    #[test]
+    fn test_auto_strategy() {
+        for cp in 0..=0x10FFFF {
+            if let Some(c) = char::from_u32(cp) {
+                let category = age::uniprops::Category::from_char(c).map(|c| c as u8);
+                let age = age::uniprops::age(c).map(|v| (v.major, v.minor));
+                let xid_start = identifiers::uniprops::is_xid_start(c);
+
+                assert_eq!(
+                    auto_size::uniprops::Category::from_char(c).map(|c| c as u8),
+                    category,
+                    "U+{cp:04X}"
+                );
+                assert_eq!(
+                    auto_size::uniprops::age(c).map(|v| (v.major, v.minor)),
+                    age,
+                    "U+{cp:04X}"
+                );
+                assert_eq!(auto_size::uniprops::is_xid_start(c), xid_start);
+                assert_eq!(
+                    auto_speed::uniprops::Category::from_char(c).map(|c| c as u8),
+                    category,
+                    "U+{cp:04X}"
+                );
+                assert_eq!(
+                    auto_speed::uniprops::age(c).map(|v| (v.major, v.minor)),
+                    age,
+                    "U+{cp:04X}"
+                );
+                assert_eq!(auto_speed::uniprops::is_xid_start(c), xid_start);
+                assert_eq!(
+                    auto_filtered_digits::uniprops::Category::from_char(c).map(|c| c as u8),
+                    filtered_digits::uniprops::Category::from_char(c).map(|c| c as u8),
+                    "U+{cp:04X}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_category_fast_path() {
+        use ascii_fast_path_bsearch::uniprops::Category as Filtered;
+        use latin1_fast_path::uniprops::Category as Latin1;
+
+        for cp in 0..=0x10FFFF {
+            if let Some(c) = char::from_u32(cp) {
+                assert_eq!(
+                    Latin1::from_char(c).map(|c| c as u8),
+                    generated_uniprops::uniprops::Category::from_char(c).map(|c| c as u8),
+                    "U+{cp:04X}"
+                );
+            }
+        }
+
+        // The fast path respects `.filter()`
+        assert_eq!(Filtered::from_char(' '), Some(Filtered::Zs));
+        assert_eq!(Filtered::from_char('5'), Some(Filtered::Nd));
+        assert_eq!(Filtered::from_char('A'), None);
+        assert_eq!(Filtered::from_char('\u{7F}'), None);
+        assert_eq!(Filtered::from_char('\u{661}'), Some(Filtered::Nd));
+        assert_eq!(Filtered::from_char('\u{E9}'), None);
+    }
+
+    #[test]
+    fn test_eytzinger_matches_bsearch() {
+        for cp in 0..=0x10FFFF {
+            if let Some(c) = char::from_u32(cp) {
+                let category = bsearch::uniprops::Category::from_char(c).map(|c| c as u8);
+                assert_eq!(
+                    eytzinger::uniprops::Category::from_char(c).map(|c| c as u8),
+                    category,
+                    "U+{cp:04X}"
+                );
+                assert_eq!(
+                    generated_uniprops::uniprops::Category::from_char(c).map(|c| c as u8),
+                    category,
+                    "U+{cp:04X}"
+                );
+                assert_eq!(
+                    eytzinger::uniprops::age(c).map(|v| (v.major, v.minor)),
+                    age::uniprops::age(c).map(|v| (v.major, v.minor)),
+                    "U+{cp:04X}"
+                );
+                assert_eq!(
+                    eytzinger::uniprops::is_xid_start(c),
+                    identifiers::uniprops::is_xid_start(c),
+                    "U+{cp:04X}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_perfect_hash_matches_bsearch() {
+        for cp in 0..=0x10FFFF {
+            if let Some(c) = char::from_u32(cp) {
+                assert_eq!(
+                    perfect_hash_nd::uniprops::Category::from_char(c).map(|c| c as u8),
+                    bsearch_nd::uniprops::Category::from_char(c).map(|c| c as u8),
+                    "U+{cp:04X}"
+                );
+                assert_eq!(
+                    perfect_hash_nd::uniprops::get_digit_value(c),
+                    generated_uniprops::uniprops::get_digit_value(c)
+                );
+                assert_eq!(
+                    perfect_hash_scattered::uniprops::Category::from_char(c).map(|c| c as u8),
+                    bsearch_scattered::uniprops::Category::from_char(c).map(|c| c as u8),
+                    "U+{cp:04X}"
+                );
+                assert_eq!(
+                    perfect_hash_full::uniprops::Category::from_char(c).map(|c| c as u8),
+                    generated_uniprops::uniprops::Category::from_char(c).map(|c| c as u8),
+                    "U+{cp:04X}"
+                );
+                assert_eq!(
+                    perfect_hash_full::uniprops::get_digit_value(c),
+                    generated_uniprops::uniprops::get_digit_value(c)
+                );
+            }
+        }
+        assert_eq!(
+            perfect_hash_scattered::uniprops::Category::from_char('D'),
+            Some(perfect_hash_scattered::uniprops::Category::Lu)
+        );
+        assert_eq!(
+            perfect_hash_scattered::uniprops::Category::from_char('E'),
+            None
+        );
+    }
+
+    #[test]
+    fn test_category_from_utf8() {
+        let mut buf = [0u8; 8];
+        for cp in 0..=0x10FFFF {
+            if let Some(c) = char::from_u32(cp) {
+                let len = c.encode_utf8(&mut buf).len();
+                // Trailing bytes must not be consumed
+                buf[len..].fill(b'a');
+                let expected = generated_uniprops::uniprops::Category::from_char(c)
+                    .map(|category| (category as u8, len));
+                assert_eq!(
+                    utf8_lookup::uniprops::Category::from_utf8(&buf)
+                        .map(|(category, len)| (category as u8, len)),
+                    expected,
+                    "U+{cp:04X}"
+                );
+                assert_eq!(
+                    utf8_lookup_packed::uniprops::Category::from_utf8(&buf[..len])
+                        .map(|(category, len)| (category as u8, len)),
+                    expected,
+                    "U+{cp:04X}"
+                );
+                assert_eq!(
+                    utf8_lookup::uniprops::Category::from_utf8(&buf[..len - 1]),
+                    None,
+                    "Truncated U+{cp:04X}"
+                );
+            }
+        }
+
+        let invalid: [&[u8]; 12] = [
+            b"",
+            b"\x80",
+            b"\xBF",
+            b"\xC0\x80",
+            b"\xC1\xBF",
+            b"\xC3\x28",
+            b"\xE0\x80\x80",
+            b"\xE0\x9F\xBF",
+            b"\xED\xA0\x80",
+            b"\xF0\x80\x80\x80",
+            b"\xF4\x90\x80\x80",
+            b"\xF5\x80\x80\x80",
+        ];
+        for bytes in invalid {
+            assert_eq!(
+                utf8_lookup::uniprops::Category::from_utf8(bytes),
+                None,
+                "{bytes:X?}"
+            );
+            assert_eq!(
+                utf8_lookup_packed::uniprops::Category::from_utf8(bytes),
+                None,
+                "{bytes:X?}"
+            );
+        }
+
+        assert_eq!(
+            utf8_lookup::uniprops::Category::from_utf8("🦀 crab".as_bytes()),
+            Some((utf8_lookup::uniprops::Category::So, 4))
+        );
+    }
+
+    #[test]
+    fn test_batch_lookups() {
+        use batch_lookups::uniprops::{Category, get_digit_value, get_digit_values};
+
+        // ASCII runs of every length around the chunk size, between non-ASCII characters
+        let mut text = String::new();
+        for len in 0..40 {
+            text.extend(('!'..='~').cycle().skip(len).take(len));
+            text.push(['é', '٣', '𝟘', '🦀'][len % 4]);
+        }
+        text.push_str("0123456789abcdef0123456789");
+        let chars: Vec<char> = text.chars().collect();
+
+        let mut categories = vec![None; chars.len()];
+        assert_eq!(Category::classify_str(&text, &mut categories), chars.len());
+        for (c, category) in chars.iter().zip(&categories) {
+            assert_eq!(
+                category.map(|c| c as u8),
+                Category::from_char(*c).map(|c| c as u8),
+                "{c:?}"
+            );
+        }
+
+        // A shorter output stops the classification, even inside an ASCII run
+        let mut short = [None; 20];
+        assert_eq!(Category::classify_str(&text, &mut short), 20);
+        assert_eq!(short, categories[..20]);
+        assert_eq!(Category::classify_str("abc", &mut categories), 3);
+
+        let mut values = vec![None; chars.len()];
+        assert_eq!(get_digit_values(&chars, &mut values), chars.len());
+        for (c, value) in chars.iter().zip(&values) {
+            assert_eq!(*value, get_digit_value(*c), "{c:?}");
+        }
+        assert_eq!(get_digit_values(&chars[..5], &mut values), 5);
+        assert_eq!(get_digit_values(&chars, &mut values[..7]), 7);
+
+        // The ASCII table follows the filter
+        let digits: Vec<char> = "01234567890123456789".chars().collect();
+        let mut values = [None; 20];
+        batch_lookups_without_0x38::uniprops::get_digit_values(&digits, &mut values);
+        for (c, value) in digits.iter().zip(values) {
+            let expected = if *c == '8' {
+                None
+            } else {
+                c.to_digit(10).map(|d| d as u8)
+            };
+            assert_eq!(value, expected, "{c:?}");
+        }
+    }
+
+    #[test]
+    fn test_overlapping_blocks_match_unpacked() {
+        for cp in 0..=0x10FFFF {
+            if let Some(c) = char::from_u32(cp) {
+                let category = age::uniprops::Category::from_char(c).map(|c| c as u8);
+                let age = age::uniprops::age(c).map(|v| (v.major, v.minor));
+
+                assert_eq!(
+                    trie_shift_4::uniprops::Category::from_char(c).map(|c| c as u8),
+                    category,
+                    "U+{cp:04X}"
+                );
+                assert_eq!(
+                    trie_shift_4::uniprops::age(c).map(|v| (v.major, v.minor)),
+                    age,
+                    "U+{cp:04X}"
+                );
+                assert_eq!(
+                    packed_blocks_shift_4::uniprops::Category::from_char(c).map(|c| c as u8),
+                    category,
+                    "U+{cp:04X}"
+                );
+                assert_eq!(
+                    packed_blocks_shift_4::uniprops::age(c).map(|v| (v.major, v.minor)),
+                    age,
+                    "U+{cp:04X}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_collation_assigned_since() {
+        use collation_6_1::uniprops::compare;
+        use std::cmp::Ordering;
+
+        // Unified ideographs keep the core weights up to U+9FCC, before extension A and B
+        assert_eq!(compare("\u{4E00}", "\u{9FCB}"), Ordering::Less);
+        assert_eq!(compare("\u{9FCB}", "\u{9FCC}"), Ordering::Less);
+        assert_eq!(compare("\u{9FCC}", "\u{3400}"), Ordering::Less);
+        assert_eq!(compare("\u{3400}", "\u{4DB5}"), Ordering::Less);
+        assert_eq!(compare("\u{4DB5}", "\u{20000}"), Ordering::Less);
+        assert_eq!(compare("\u{20000}", "\u{2A6D6}"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_table_value_sizes() {
+        use std::mem::size_of;
+
+        // The sizes the generator assumes when measuring the layouts of `LookupStrategy::Auto`
+        assert_eq!(size_of::<Option<age::uniprops::UnicodeVersion>>(), 3);
+        assert_eq!(size_of::<Option<unihan::uniprops::RadicalStroke>>(), 3);
+        assert_eq!(
+            size_of::<Option<generated_uniprops::uniprops::Category>>(),
+            1
+        );
+        assert_eq!(
+            size_of::<Option<&'static [identifier_security::uniprops::Script]>>(),
+            2 * size_of::<usize>()
+        );
+        assert_eq!(
+            size_of::<Option<&'static [identifier_security::uniprops::IdentifierType]>>(),
+            2 * size_of::<usize>()
+        );
+        assert_eq!(size_of::<Option<u16>>(), 4);
+        assert_eq!(size_of::<Option<u8>>(), 2);
+        assert_eq!(size_of::<Option<()>>(), 1);
+
+        // The sizes of the values stored in binary search entries and packed palettes
+        assert_eq!(size_of::<age::uniprops::UnicodeVersion>(), 2);
+        assert_eq!(size_of::<unihan::uniprops::RadicalStroke>(), 3);
+        assert_eq!(size_of::<generated_uniprops::uniprops::Category>(), 1);
+        assert_eq!(
+            size_of::<&'static [identifier_security::uniprops::Script]>(),
+            2 * size_of::<usize>()
+        );
+    }
 }
@@ -0,0 +1,99 @@
+//! Support for the `Age` property from `DerivedAge.txt`.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{MappingGroup, UnipropsBuilder, lookup::PropertyTable, ucd};
+
+pub(crate) const DERIVED_AGE: &str = include_str!("../assets/DerivedAge.txt");
+
+/// Parses an age value such as `"6.1"` into `(major, minor)`.
+fn parse_version(value: &str) -> (u8, u8) {
+    let (major, minor) = value.split_once('.').expect("Invalid age value");
+    (
+        major.parse().expect("Invalid major version"),
+        minor.parse().expect("Invalid minor version"),
+    )
+}
+
+/// The parsed contents of `DerivedAge.txt`.
+pub(crate) struct Ages {
+    groups: Vec<MappingGroup>,
+}
+
+impl Ages {
+    pub(crate) fn load() -> Self {
+        Self {
+            groups: ucd::to_groups(&ucd::parse_property_file(DERIVED_AGE), 0),
+        }
+    }
+
+    /// Returns the version in which `cp` was assigned, or `None` if it is unassigned.
+    pub(crate) fn of(&self, cp: u32) -> Option<(u8, u8)> {
+        let idx = self.groups.partition_point(|g| g.end < cp);
+        self.groups
+            .get(idx)
+            .filter(|g| g.start <= cp)
+            .map(|g| parse_version(&g.value))
+    }
+
+    /// Clips `groups` to the codepoints assigned in `version` or earlier.
+    pub(crate) fn restrict(&self, groups: &[MappingGroup], version: (u8, u8)) -> Vec<MappingGroup> {
+        let assigned = self
+            .groups
+            .iter()
+            .filter(|g| parse_version(&g.value) <= version)
+            .collect::<Vec<_>>();
+
+        let mut restricted = Vec::new();
+        for group in groups {
+            let first = assigned.partition_point(|a| a.end < group.start);
+            for a in assigned[first..]
+                .iter()
+                .take_while(|a| a.start <= group.end)
+            {
+                restricted.push(MappingGroup {
+                    value: group.value.clone(),
+                    start: group.start.max(a.start),
+                    end: group.end.min(a.end),
+                });
+            }
+        }
+        restricted
+    }
+}
+
+impl UnipropsBuilder<'_> {
+    pub(crate) fn generate_age(&self, ages: &Ages) -> TokenStream {
+        let groups = self.restrict_to_assigned(Some(ages), ages.groups.clone());
+        let table = PropertyTable {
+            name: "Age",
+            value_type: quote! { UnicodeVersion },
+            value_size: 2,
+            option_size: 3,
+            groups: &groups,
+            value_tokens: &|value| {
+                let (major, minor) = parse_version(value);
+                quote! { UnicodeVersion { major: #major, minor: #minor } }
+            },
+        };
+        let (statics, body) = table.generate(self.table_options());
+
+        quote! {
+            /// A version of the Unicode Standard.
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+            pub struct UnicodeVersion {
+                pub major: u8,
+                pub minor: u8,
+            }
+
+            #statics
+
+            /// Returns the version of Unicode in which `c` was first assigned.
+            #[inline(always)]
+            pub fn age(c: char) -> ::std::option::Option<UnicodeVersion> {
+                #body
+            }
+        }
+    }
+}
@@ -0,0 +1,60 @@
+//! Generation of `is_*` predicates for binary properties, e.g. `XID_Start`, listed in
+//! multi-property files such as `DerivedCoreProperties.txt`.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use crate::{MappingGroup, UnipropsBuilder, lookup::PropertyTable};
+
+pub(crate) const DERIVED_CORE_PROPERTIES: &str =
+    include_str!("../assets/DerivedCoreProperties.txt");
+pub(crate) const DERIVED_NORMALIZATION_PROPS: &str =
+    include_str!("../assets/DerivedNormalizationProps.txt");
+
+/// `XID_Start` -> `XidStart`, used to name the tables.
+fn table_name(property: &str) -> String {
+    property
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_string() + &chars.as_str().to_ascii_lowercase(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+impl UnipropsBuilder<'_> {
+    /// Generates `fn_name(c: char) -> bool`, returning `true` for the codepoints in `groups`.
+    pub(crate) fn generate_binary_property(
+        &self,
+        property: &str,
+        fn_name: &str,
+        visibility: TokenStream,
+        groups: &[MappingGroup],
+    ) -> TokenStream {
+        let name = table_name(property);
+        let table = PropertyTable {
+            name: &name,
+            value_type: quote! { () },
+            value_size: 0,
+            option_size: 1,
+            groups,
+            value_tokens: &|_| quote! { () },
+        };
+        let (statics, body) = table.generate_membership(self.table_options());
+        let fn_ident = format_ident!("{}", fn_name);
+        let doc = format!(" Returns `true` if `c` has the `{property}` property.");
+
+        quote! {
+            #statics
+
+            #[doc = #doc]
+            #[inline(always)]
+            #visibility fn #fn_ident(c: char) -> bool {
+                #body
+            }
+        }
+    }
+}
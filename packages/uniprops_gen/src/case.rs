@@ -0,0 +1,196 @@
+//! Case-insensitive matching sets ("case orbits") built from the simple foldings of
+//! `CaseFolding.txt`, and the derived case properties (`Cased`, `Changes_When_*`, ...).
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{
+    Ages, MappingGroup, UnicodeRecord, UnipropsBuilder,
+    binary::{DERIVED_CORE_PROPERTIES, DERIVED_NORMALIZATION_PROPS},
+    lookup::PropertyTable,
+    ucd,
+};
+
+pub(crate) const CASE_FOLDING: &str = include_str!("../assets/CaseFolding.txt");
+
+/// `(source file, property, generated function)` of the derived case properties.
+const CASE_PROPERTIES: &[(&str, &str, &str)] = &[
+    (DERIVED_CORE_PROPERTIES, "Cased", "is_cased"),
+    (
+        DERIVED_CORE_PROPERTIES,
+        "Case_Ignorable",
+        "is_case_ignorable",
+    ),
+    (
+        DERIVED_CORE_PROPERTIES,
+        "Changes_When_Lowercased",
+        "changes_when_lowercased",
+    ),
+    (
+        DERIVED_CORE_PROPERTIES,
+        "Changes_When_Uppercased",
+        "changes_when_uppercased",
+    ),
+    (
+        DERIVED_CORE_PROPERTIES,
+        "Changes_When_Titlecased",
+        "changes_when_titlecased",
+    ),
+    (
+        DERIVED_CORE_PROPERTIES,
+        "Changes_When_Casefolded",
+        "changes_when_casefolded",
+    ),
+    (
+        DERIVED_CORE_PROPERTIES,
+        "Changes_When_Casemapped",
+        "changes_when_casemapped",
+    ),
+    (
+        DERIVED_NORMALIZATION_PROPS,
+        "Changes_When_NFKC_Casefolded",
+        "changes_when_nfkc_casefolded",
+    ),
+];
+
+/// A minimal union-find over codepoints.
+struct Orbits {
+    parents: BTreeMap<u32, u32>,
+}
+
+impl Orbits {
+    fn find(&mut self, cp: u32) -> u32 {
+        let parent = *self.parents.entry(cp).or_insert(cp);
+        if parent == cp {
+            return cp;
+        }
+        let root = self.find(parent);
+        self.parents.insert(cp, root);
+        root
+    }
+
+    fn union(&mut self, a: u32, b: u32) {
+        let (a, b) = (self.find(a), self.find(b));
+        if a != b {
+            self.parents.insert(a.max(b), a.min(b));
+        }
+    }
+}
+
+impl UnipropsBuilder<'_> {
+    /// Generates `case_orbit`. Only characters present in `records` (the filtered records)
+    /// take part in the orbits.
+    pub(crate) fn generate_case_orbits(&self, records: &[UnicodeRecord]) -> TokenStream {
+        let present = records
+            .iter()
+            .map(|r| r.code_point)
+            .collect::<BTreeSet<_>>();
+        let mut orbits = Orbits {
+            parents: BTreeMap::new(),
+        };
+        let mut link = |a: u32, b: u32| {
+            if a != b && present.contains(&a) && present.contains(&b) {
+                orbits.union(a, b);
+            }
+        };
+
+        // Characters with the same common or simple folding share an orbit, like the case closure
+        // of ICU and `regex`. Linking the case mappings instead would merge the Turkic `İ` and
+        // `ı` into the orbit of `i`, which only the `T` foldings relate.
+        for entry in ucd::parse_property_file(CASE_FOLDING) {
+            if matches!(entry.fields[0], "C" | "S") {
+                link(entry.start, ucd::parse_code_point(entry.fields[1]));
+            }
+        }
+
+        let mut classes: BTreeMap<u32, Vec<u32>> = BTreeMap::new();
+        for cp in orbits.parents.keys().copied().collect::<Vec<_>>() {
+            let root = orbits.find(cp);
+            classes.entry(root).or_default().push(cp);
+        }
+
+        // Every orbit is stored once in CASE_ORBIT_CHARS, the table maps each member to the
+        // index of its orbit
+        let mut chars = Vec::new();
+        let mut offsets = vec![0u16];
+        let mut members = Vec::new();
+        for (index, class) in classes.values().enumerate() {
+            chars.extend(class.iter().map(|&cp| char::from_u32(cp).unwrap()));
+            offsets.push(u16::try_from(chars.len()).expect("Case orbits exceed u16 offsets"));
+            members.extend(class.iter().map(|&cp| (cp, index)));
+        }
+        members.sort();
+
+        let mut groups: Vec<MappingGroup> = Vec::new();
+        for (cp, index) in members {
+            let value = index.to_string();
+            match groups.last_mut() {
+                Some(last) if last.end + 1 == cp && last.value == value => last.end = cp,
+                _ => groups.push(MappingGroup {
+                    value,
+                    start: cp,
+                    end: cp,
+                }),
+            }
+        }
+
+        let table = PropertyTable {
+            name: "CaseOrbit",
+            value_type: quote! { u16 },
+            value_size: 2,
+            option_size: 4,
+            groups: &groups,
+            value_tokens: &|value| {
+                let value: u16 = value.parse().unwrap();
+                quote! { #value }
+            },
+        };
+        let (statics, body) = table.generate(self.table_options());
+
+        let offsets_len = offsets.len();
+        let chars_len = chars.len();
+
+        quote! {
+            #statics
+
+            static CASE_ORBIT_OFFSETS: [u16; #offsets_len] = [ #(#offsets),* ];
+            static CASE_ORBIT_CHARS: [char; #chars_len] = [ #(#chars),* ];
+
+            /// Returns every character which matches `c` case-insensitively, including `c`
+            /// itself and sorted by codepoint, e.g. `['K', 'k', '\u{212A}']` for `k`.
+            ///
+            /// Returns an empty slice if `c` only matches itself.
+            #[inline]
+            pub fn case_orbit(c: char) -> &'static [char] {
+                let orbit: ::std::option::Option<u16> = { #body };
+                match orbit {
+                    // SAFETY: Orbit indices are generated from the positions in OFFSETS, whose
+                    // values are valid positions in CHARS
+                    ::std::option::Option::Some(i) => unsafe {
+                        let start = *CASE_ORBIT_OFFSETS.get_unchecked(i as usize) as usize;
+                        let end = *CASE_ORBIT_OFFSETS.get_unchecked(i as usize + 1) as usize;
+                        CASE_ORBIT_CHARS.get_unchecked(start..end)
+                    },
+                    ::std::option::Option::None => &[],
+                }
+            }
+        }
+    }
+
+    pub(crate) fn generate_case_properties(&self, ages: Option<&Ages>) -> TokenStream {
+        CASE_PROPERTIES
+            .iter()
+            .map(|&(source, property, fn_name)| {
+                let groups = ucd::binary_property_groups(source, property);
+                self.generate_binary_property(
+                    property,
+                    fn_name,
+                    quote! { pub },
+                    &self.restrict_to_assigned(ages, groups),
+                )
+            })
+            .collect()
+    }
+}
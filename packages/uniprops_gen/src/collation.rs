@@ -0,0 +1,286 @@
+//! Unicode Collation Algorithm (UTS #10) with the Default Unicode Collation Element Table from
+//! `allkeys.txt`.
+//!
+//! The generated collation uses non-ignorable variable weighting (as the CLDR root collation
+//! does) and does not support discontiguous contractions. Strings are normalized with the
+//! generated `nfd` first, so Hangul syllables are collated through their jamo.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{Ages, PositionTag, UnicodeRecord, UnipropsBuilder, get_tag_by_name, ucd};
+
+pub(crate) const ALLKEYS: &str = include_str!("../assets/allkeys.txt");
+
+/// Compatibility ideographs which are nevertheless `Unified_Ideograph`.
+const UNIFIED_COMPATIBILITY_IDEOGRAPHS: &[u32] = &[
+    0xFA0E, 0xFA0F, 0xFA11, 0xFA13, 0xFA14, 0xFA1F, 0xFA21, 0xFA23, 0xFA24, 0xFA27, 0xFA28, 0xFA29,
+];
+
+/// A collation element packed as `primary << 16 | secondary << 5 | tertiary`.
+fn pack_element(element: &str) -> u32 {
+    // `.0000.0020.0002` or `*0209.0020.0002`, the first character marks variable elements
+    let mut weights = element[1..]
+        .split('.')
+        .map(|w| u32::from_str_radix(w, 16).expect("Invalid weight in allkeys.txt"));
+    let mut next = || weights.next().expect("Missing weight in allkeys.txt");
+    let (primary, secondary, tertiary) = (next(), next(), next());
+    assert!(
+        secondary < 1 << 11 && tertiary < 1 << 5,
+        "Weight out of range in allkeys.txt"
+    );
+    (primary << 16) | (secondary << 5) | tertiary
+}
+
+/// A parsed `allkeys.txt` line: the source codepoints and their collation elements.
+type CollationEntry = (Vec<u32>, Vec<u32>);
+
+fn parse_allkeys() -> (Vec<CollationEntry>, Vec<(u32, u32, u32)>) {
+    let mut entries = Vec::new();
+    let mut implicit = Vec::new();
+    for line in ALLKEYS.lines() {
+        let data = line.split('#').next().unwrap_or("").trim();
+        if let Some(weights) = data.strip_prefix("@implicitweights") {
+            let (range, base) = weights.split_once(';').expect("Invalid @implicitweights");
+            let (start, end) = ucd::parse_range(range);
+            implicit.push((start, end, ucd::parse_code_point(base)));
+            continue;
+        }
+        if data.is_empty() || data.starts_with('@') {
+            continue;
+        }
+
+        let (source, elements) = data.split_once(';').expect("Invalid line in allkeys.txt");
+        let source = source
+            .split_whitespace()
+            .map(ucd::parse_code_point)
+            .collect();
+        let elements = elements
+            .split(['[', ']'])
+            .map(str::trim)
+            .filter(|e| !e.is_empty())
+            .map(pack_element)
+            .collect();
+        entries.push((source, elements));
+    }
+    (entries, implicit)
+}
+
+impl UnipropsBuilder<'_> {
+    /// Generates the collation tables, `compare` and `sort_key`. Requires the output of
+    /// [`Self::generate_normalization`] in the same module.
+    pub(crate) fn generate_collation(
+        &self,
+        records: &[UnicodeRecord],
+        ages: Option<&Ages>,
+    ) -> TokenStream {
+        let is_assigned = |cp: &u32| self.is_assigned(ages, *cp);
+        let (entries, implicit) = parse_allkeys();
+        let (mut singles, mut contractions): (Vec<_>, Vec<_>) = entries
+            .into_iter()
+            .filter(|(source, _)| source.iter().all(is_assigned))
+            .partition(|(source, _)| source.len() == 1);
+        singles.sort();
+        contractions.sort();
+
+        let mut elements = Vec::new();
+        let mut offsets = vec![0u16];
+        let mut push_elements = |ces: &[u32]| {
+            let start = elements.len() as u16;
+            elements.extend_from_slice(ces);
+            let end = u16::try_from(elements.len()).expect("Collation table exceeds u16 offsets");
+            (start, end)
+        };
+        let sources = singles
+            .iter()
+            .map(|(source, ces)| {
+                offsets.push(push_elements(ces).1);
+                source[0]
+            })
+            .collect::<Vec<_>>();
+        let contractions = contractions
+            .iter()
+            .map(|(source, ces)| {
+                let (start, end) = push_elements(ces);
+                let chars = source.iter().map(|&cp| char::from_u32(cp).unwrap());
+                quote! { (&[#(#chars),*], #start, #end) }
+            })
+            .collect::<Vec<_>>();
+
+        // Implicit weights: `(start, end, base, origin)`. Unified ideographs compute their
+        // weights from the codepoint itself, the other blocks from the start of the block.
+        let mut implicit_weights = Vec::new();
+        for &(start, end, base) in &implicit {
+            let origin = implicit
+                .iter()
+                .filter(|(_, _, b)| *b == base)
+                .map(|(s, _, _)| *s)
+                .min()
+                .unwrap();
+            implicit_weights.push((start, end, base, origin));
+        }
+        // A `Last` record ends the range of the preceding `First` record, which is alone when
+        // `assigned_since` clips the range to a single codepoint
+        let mut ideographs: Vec<(u32, u32)> = Vec::new();
+        for record in records
+            .iter()
+            .filter(|r| r.name.starts_with("<CJK Ideograph"))
+        {
+            match (get_tag_by_name(&record.name), ideographs.last_mut()) {
+                (PositionTag::Last, Some((_, end))) => *end = record.code_point,
+                _ => ideographs.push((record.code_point, record.code_point)),
+            }
+        }
+        for &(start, end) in &ideographs {
+            let is_core = (0x4E00..=0x9FFF).contains(&start);
+            implicit_weights.push((start, end, if is_core { 0xFB40 } else { 0xFB80 }, 0));
+        }
+        for &cp in UNIFIED_COMPATIBILITY_IDEOGRAPHS
+            .iter()
+            .filter(|cp| is_assigned(cp))
+        {
+            implicit_weights.push((cp, cp, 0xFB40, 0));
+        }
+        implicit_weights.sort();
+        let implicit_weights = implicit_weights.iter().map(|(start, end, base, origin)| {
+            let base = *base as u16;
+            quote! { (#start, #end, #base, #origin) }
+        });
+
+        let sources_len = sources.len();
+        let offsets_len = offsets.len();
+        let elements_len = elements.len();
+        let contractions_len = contractions.len();
+        let implicit_len = implicit_weights.len();
+
+        quote! {
+            static COLLATION_SOURCES: [u32; #sources_len] = [ #(#sources),* ];
+            static COLLATION_OFFSETS: [u16; #offsets_len] = [ #(#offsets),* ];
+            static COLLATION_ELEMENTS: [u32; #elements_len] = [ #(#elements),* ];
+            static COLLATION_CONTRACTIONS: [(&[char], u16, u16); #contractions_len] = [
+                #(#contractions),*
+            ];
+            static COLLATION_IMPLICIT_WEIGHTS: [(u32, u32, u16, u32); #implicit_len] = [
+                #(#implicit_weights),*
+            ];
+
+            /// The number of levels compared by the collation.
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+            pub enum CollationStrength {
+                /// Base letters only: `a = á = A`.
+                Primary,
+                /// Base letters and accents: `a = A < á`.
+                Secondary,
+                /// Base letters, accents and case: `a < A < á`.
+                #[default]
+                Tertiary,
+            }
+
+            fn collation_lookup(c: char) -> ::std::option::Option<&'static [u32]> {
+                let cp = c as u32;
+                COLLATION_SOURCES.binary_search(&cp).ok().map(|i| {
+                    // SAFETY: OFFSETS has one more element than SOURCES and its values are
+                    // valid positions in ELEMENTS
+                    unsafe {
+                        let start = *COLLATION_OFFSETS.get_unchecked(i) as usize;
+                        let end = *COLLATION_OFFSETS.get_unchecked(i + 1) as usize;
+                        COLLATION_ELEMENTS.get_unchecked(start..end)
+                    }
+                })
+            }
+
+            /// Returns the length and the collation elements of the longest contraction at the
+            /// start of `chars`.
+            fn collation_contraction(chars: &[char]) -> ::std::option::Option<(usize, &'static [u32])> {
+                let first = chars[0];
+                let start = COLLATION_CONTRACTIONS.partition_point(|(source, _, _)| source[0] < first);
+                COLLATION_CONTRACTIONS[start..]
+                    .iter()
+                    .take_while(|(source, _, _)| source[0] == first)
+                    .filter(|(source, _, _)| chars.starts_with(source))
+                    .max_by_key(|(source, _, _)| source.len())
+                    .map(|&(source, start, end)| {
+                        (source.len(), &COLLATION_ELEMENTS[start as usize..end as usize])
+                    })
+            }
+
+            /// Pushes the implicit collation elements of a character missing from the table.
+            fn push_implicit_elements(c: char, out: &mut ::std::vec::Vec<u32>) {
+                let cp = c as u32;
+                let (base, origin) = COLLATION_IMPLICIT_WEIGHTS
+                    .binary_search_by(|&(start, end, _, _)| {
+                        if cp < start {
+                            ::core::cmp::Ordering::Greater
+                        } else if cp > end {
+                            ::core::cmp::Ordering::Less
+                        } else {
+                            ::core::cmp::Ordering::Equal
+                        }
+                    })
+                    .map_or((0xFBC0, 0), |i| {
+                        let (_, _, base, origin) = COLLATION_IMPLICIT_WEIGHTS[i];
+                        (base as u32, origin)
+                    });
+                let offset = cp - origin;
+                out.push(((base + (offset >> 15)) << 16) | (0x20 << 5) | 0x02);
+                out.push(((offset & 0x7FFF) | 0x8000) << 16);
+            }
+
+            /// Returns the collation elements of `s`, packed as
+            /// `primary << 16 | secondary << 5 | tertiary`.
+            fn collation_elements(s: &str) -> ::std::vec::Vec<u32> {
+                let chars = nfd(s).chars().collect::<::std::vec::Vec<_>>();
+                let mut elements = ::std::vec::Vec::with_capacity(chars.len());
+                let mut i = 0;
+                while i < chars.len() {
+                    if let ::std::option::Option::Some((len, ces)) = collation_contraction(&chars[i..]) {
+                        elements.extend_from_slice(ces);
+                        i += len;
+                        continue;
+                    }
+                    match collation_lookup(chars[i]) {
+                        ::std::option::Option::Some(ces) => elements.extend_from_slice(ces),
+                        ::std::option::Option::None => push_implicit_elements(chars[i], &mut elements),
+                    }
+                    i += 1;
+                }
+                elements
+            }
+
+            /// Returns the UCA sort key of `s` at the given strength. Comparing sort keys is
+            /// equivalent to [`compare_with_strength`].
+            pub fn sort_key_with_strength(s: &str, strength: CollationStrength) -> ::std::vec::Vec<u16> {
+                let elements = collation_elements(s);
+                let mut key = ::std::vec::Vec::with_capacity(elements.len() * 3 + 2);
+                key.extend(elements.iter().map(|e| (e >> 16) as u16).filter(|&w| w != 0));
+                if strength >= CollationStrength::Secondary {
+                    key.push(0);
+                    key.extend(elements.iter().map(|e| ((e >> 5) & 0x7FF) as u16).filter(|&w| w != 0));
+                }
+                if strength >= CollationStrength::Tertiary {
+                    key.push(0);
+                    key.extend(elements.iter().map(|e| (e & 0x1F) as u16).filter(|&w| w != 0));
+                }
+                key
+            }
+
+            /// Returns the UCA sort key of `s` at tertiary strength.
+            pub fn sort_key(s: &str) -> ::std::vec::Vec<u16> {
+                sort_key_with_strength(s, CollationStrength::Tertiary)
+            }
+
+            /// Compares `a` and `b` according to the root collation at the given strength.
+            ///
+            /// Strings which differ only beyond `strength` compare as equal; chain
+            /// `.then_with(|| a.cmp(b))` for a total order.
+            pub fn compare_with_strength(a: &str, b: &str, strength: CollationStrength) -> ::core::cmp::Ordering {
+                sort_key_with_strength(a, strength).cmp(&sort_key_with_strength(b, strength))
+            }
+
+            /// Compares `a` and `b` according to the root collation at tertiary strength.
+            pub fn compare(a: &str, b: &str) -> ::core::cmp::Ordering {
+                compare_with_strength(a, b, CollationStrength::Tertiary)
+            }
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! UTS #39 confusable detection based on `confusables.txt`.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{
+    MappingGroup, UnipropsBuilder, binary::DERIVED_CORE_PROPERTIES,
+    lookup::generate_sequence_table, ucd,
+};
+
+pub(crate) const CONFUSABLES: &str = include_str!("../assets/confusables.txt");
+
+impl UnipropsBuilder<'_> {
+    /// Generates the prototype table and the `skeleton` function of UTS #39 version 15.1 and
+    /// later, which drops default ignorable characters. Requires the output of
+    /// [`Self::generate_normalization`] in the same module.
+    ///
+    /// Only sources covered by `groups` (the filtered records) are mapped.
+    pub(crate) fn generate_confusables(&self, groups: &[MappingGroup]) -> TokenStream {
+        let is_selected = |cp: u32| {
+            let idx = groups.partition_point(|g| g.end < cp);
+            groups.get(idx).is_some_and(|g| g.start <= cp)
+        };
+
+        let mut prototypes = ucd::parse_property_file(CONFUSABLES)
+            .into_iter()
+            .filter(|entry| is_selected(entry.start))
+            .map(|entry| {
+                let target = entry.fields[0]
+                    .split_whitespace()
+                    .map(ucd::parse_code_point)
+                    .collect::<Vec<_>>();
+                (entry.start, target)
+            })
+            .collect::<Vec<_>>();
+        prototypes.sort();
+
+        let (statics, body) = generate_sequence_table("CONFUSABLE", &prototypes);
+        let default_ignorable = self.generate_binary_property(
+            "Default_Ignorable_Code_Point",
+            "is_default_ignorable",
+            quote! {},
+            &ucd::binary_property_groups(DERIVED_CORE_PROPERTIES, "Default_Ignorable_Code_Point"),
+        );
+
+        quote! {
+            #statics
+
+            #default_ignorable
+
+            /// Returns the prototype `c` is visually confusable with, if any.
+            #[inline(always)]
+            pub fn confusable_prototype(c: char) -> ::std::option::Option<&'static [char]> {
+                #body
+            }
+
+            /// Computes the UTS #39 skeleton of `s`: `NFD`, remove default ignorable characters,
+            /// map every character to its confusable prototype, `NFD` again.
+            pub fn skeleton(s: &str) -> ::std::string::String {
+                let mut mapped = ::std::string::String::with_capacity(s.len());
+                for c in nfd(s).chars().filter(|&c| !is_default_ignorable(c)) {
+                    match confusable_prototype(c) {
+                        ::std::option::Option::Some(prototype) => mapped.extend(prototype),
+                        ::std::option::Option::None => mapped.push(c),
+                    }
+                }
+                nfd(&mapped)
+            }
+
+            /// Returns `true` if `a` and `b` have the same skeleton, i.e. are visually confusable.
+            pub fn is_confusable(a: &str, b: &str) -> bool {
+                skeleton(a) == skeleton(b)
+            }
+        }
+    }
+}
@@ -0,0 +1,246 @@
+//! Recognition of RGI emoji sequences from `emoji-sequences.txt` and
+//! `emoji-zwj-sequences.txt`, and decoding of RGI flags into ISO 3166 region codes.
+
+use std::collections::HashMap;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{Ages, UnicodeRecord, UnipropsBuilder, enumerated::variant_ident, ucd};
+
+const EMOJI_SEQUENCES: &str = include_str!("../assets/emoji-sequences.txt");
+const EMOJI_ZWJ_SEQUENCES: &str = include_str!("../assets/emoji-zwj-sequences.txt");
+
+/// REGIONAL INDICATOR SYMBOL LETTER A
+const REGIONAL_INDICATOR_A: u32 = 0x1F1E6;
+
+const EMOJI_SEQUENCE_KINDS: &[&str] = &[
+    "Basic_Emoji",
+    "Emoji_Keycap_Sequence",
+    "RGI_Emoji_Flag_Sequence",
+    "RGI_Emoji_Tag_Sequence",
+    "RGI_Emoji_Modifier_Sequence",
+    "RGI_Emoji_ZWJ_Sequence",
+];
+
+/// Replaces `\x{23}` escapes, which the files use for characters such as `#` in names.
+fn unescape_name(name: &str) -> String {
+    let mut unescaped = String::with_capacity(name.len());
+    let mut rest = name;
+    while let Some(start) = rest.find("\\x{") {
+        unescaped.push_str(&rest[..start]);
+        let (hex, tail) = rest[start + 3..]
+            .split_once('}')
+            .expect("Unterminated escape in emoji name");
+        unescaped.push(char::from_u32(ucd::parse_code_point(hex)).expect("Invalid escape"));
+        rest = tail;
+    }
+    unescaped.push_str(rest);
+    unescaped
+}
+
+/// A single RGI emoji sequence: its codepoints, type field and name.
+pub(crate) struct EmojiSequence<'s> {
+    pub code_points: Vec<u32>,
+    pub kind: &'s str,
+    pub name: String,
+}
+
+/// Parses the emoji sequence files.
+///
+/// Ranges such as `231A..231B ; Basic_Emoji ; watch..hourglass done` only name their first and
+/// last character, the ones in between are named after their lowercased `UnicodeData.txt` name.
+pub(crate) fn parse_emoji_sequences(records: &[UnicodeRecord]) -> Vec<EmojiSequence<'static>> {
+    let names = records
+        .iter()
+        .map(|r| (r.code_point, r.name.as_str()))
+        .collect::<HashMap<_, _>>();
+
+    let mut sequences = Vec::new();
+    for line in EMOJI_SEQUENCES.lines().chain(EMOJI_ZWJ_SEQUENCES.lines()) {
+        let data = line.split('#').next().unwrap_or("").trim();
+        if data.is_empty() {
+            continue;
+        }
+        let mut fields = data.split(';').map(str::trim);
+        let code_points = fields.next().expect("Missing emoji sequence");
+        let kind = fields.next().expect("Missing emoji sequence type");
+        let name = unescape_name(fields.next().expect("Missing emoji sequence name"));
+
+        if let Some((start, end)) = code_points.split_once("..") {
+            let (start, end) = (ucd::parse_code_point(start), ucd::parse_code_point(end));
+            let (first_name, last_name) = name.split_once("..").unwrap_or((&name, &name));
+            for cp in start..=end {
+                let name = if cp == start {
+                    first_name.to_string()
+                } else if cp == end {
+                    last_name.to_string()
+                } else {
+                    names
+                        .get(&cp)
+                        .map_or_else(String::new, |n| n.to_lowercase())
+                };
+                sequences.push(EmojiSequence {
+                    code_points: vec![cp],
+                    kind,
+                    name,
+                });
+            }
+        } else {
+            sequences.push(EmojiSequence {
+                code_points: code_points
+                    .split_whitespace()
+                    .map(ucd::parse_code_point)
+                    .collect(),
+                kind,
+                name,
+            });
+        }
+    }
+    sequences
+}
+
+impl UnipropsBuilder<'_> {
+    pub(crate) fn generate_emoji_sequences(
+        &self,
+        records: &[UnicodeRecord],
+        ages: Option<&Ages>,
+    ) -> TokenStream {
+        let mut sequences = parse_emoji_sequences(records)
+            .into_iter()
+            .filter(|s| s.code_points.iter().all(|&cp| self.is_assigned(ages, cp)))
+            .map(|s| {
+                let text = s
+                    .code_points
+                    .iter()
+                    .map(|&cp| char::from_u32(cp).unwrap())
+                    .collect::<String>();
+                (text, s.kind, s.name)
+            })
+            .collect::<Vec<_>>();
+        // Byte order of UTF-8 matches codepoint order, which the prefix search relies on
+        sequences.sort();
+
+        let len = sequences.len();
+        let sequences = sequences.iter().map(|(text, kind, name)| {
+            let kind = variant_ident(kind);
+            quote! { (#text, #name, EmojiSequenceKind::#kind) }
+        });
+        let kinds = EMOJI_SEQUENCE_KINDS.iter().map(|kind| variant_ident(kind));
+
+        quote! {
+            /// The type field of an RGI emoji sequence.
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            pub enum EmojiSequenceKind {
+                #(#kinds),*
+            }
+
+            static EMOJI_SEQUENCES: [(&str, &str, EmojiSequenceKind); #len] = [
+                #(#sequences),*
+            ];
+
+            /// An RGI emoji sequence found by [`emoji_sequences`].
+            #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            pub struct EmojiMatch<'a> {
+                /// The matched part of the input.
+                pub text: &'a str,
+                /// The CLDR short name, e.g. `"flag: Germany"`.
+                pub name: &'static str,
+                pub kind: EmojiSequenceKind,
+            }
+
+            /// Returns the longest RGI emoji sequence at the start of `s`.
+            fn longest_emoji_sequence(s: &str) -> ::std::option::Option<&'static (&'static str, &'static str, EmojiSequenceKind)> {
+                let first = s.chars().next()?;
+                let mut buf = [0u8; 4];
+                let first = &*first.encode_utf8(&mut buf);
+                let start = EMOJI_SEQUENCES.partition_point(|(sequence, _, _)| *sequence < first);
+                EMOJI_SEQUENCES[start..]
+                    .iter()
+                    .take_while(|(sequence, _, _)| sequence.starts_with(first))
+                    .filter(|(sequence, _, _)| s.starts_with(sequence))
+                    .max_by_key(|(sequence, _, _)| sequence.len())
+            }
+
+            /// Iterator over the RGI emoji sequences of a string, see [`emoji_sequences`].
+            #[derive(Debug, Clone)]
+            pub struct EmojiSequences<'a> {
+                rest: &'a str,
+            }
+
+            impl<'a> ::std::iter::Iterator for EmojiSequences<'a> {
+                type Item = EmojiMatch<'a>;
+
+                fn next(&mut self) -> ::std::option::Option<Self::Item> {
+                    while let ::std::option::Option::Some(c) = self.rest.chars().next() {
+                        if let ::std::option::Option::Some(&(sequence, name, kind)) = longest_emoji_sequence(self.rest) {
+                            let (text, rest) = self.rest.split_at(sequence.len());
+                            self.rest = rest;
+                            return ::std::option::Option::Some(EmojiMatch { text, name, kind });
+                        }
+                        self.rest = &self.rest[c.len_utf8()..];
+                    }
+                    ::std::option::Option::None
+                }
+            }
+
+            /// Returns an iterator over the fully-qualified RGI emoji sequences in `s`, taking
+            /// the longest sequence at each position.
+            pub fn emoji_sequences(s: &str) -> EmojiSequences<'_> {
+                EmojiSequences { rest: s }
+            }
+        }
+    }
+
+    pub(crate) fn generate_flags(
+        &self,
+        records: &[UnicodeRecord],
+        ages: Option<&Ages>,
+    ) -> TokenStream {
+        let mut flags = parse_emoji_sequences(records)
+            .into_iter()
+            .filter(|s| s.kind == "RGI_Emoji_Flag_Sequence")
+            .filter(|s| s.code_points.iter().all(|&cp| self.is_assigned(ages, cp)))
+            .map(|s| {
+                let region = s
+                    .code_points
+                    .iter()
+                    .map(|&cp| b'A' + (cp - REGIONAL_INDICATOR_A) as u8)
+                    .collect::<Vec<_>>();
+                let region: [u8; 2] = region.try_into().expect("Flag sequence is not a pair");
+                region
+            })
+            .collect::<Vec<_>>();
+        flags.sort();
+
+        let len = flags.len();
+        let flags = flags.iter().map(|[a, b]| quote! { [#a, #b] });
+
+        quote! {
+            static RGI_FLAGS: [[u8; 2]; #len] = [ #(#flags),* ];
+
+            /// Returns `true` if `c` is one of the 26 regional indicator symbols
+            /// (`Regional_Indicator=Yes`).
+            #[inline(always)]
+            pub fn is_regional_indicator(c: char) -> bool {
+                ('\u{1F1E6}'..='\u{1F1FF}').contains(&c)
+            }
+
+            /// Decodes a flag made of two regional indicator symbols into its ISO 3166 region
+            /// code, e.g. `"\u{1F1E9}\u{1F1EA}"` into `*b"DE"`.
+            ///
+            /// Returns `None` if `s` is not exactly such a pair, or if the pair is not in the RGI
+            /// flag list.
+            pub fn flag_country_code(s: &str) -> ::std::option::Option<[u8; 2]> {
+                let mut chars = s.chars();
+                let (first, second) = (chars.next()?, chars.next()?);
+                if chars.next().is_some() || !is_regional_indicator(first) || !is_regional_indicator(second) {
+                    return ::std::option::Option::None;
+                }
+                let letter = |c: char| b'A' + (c as u32 - #REGIONAL_INDICATOR_A) as u8;
+                let region = [letter(first), letter(second)];
+                RGI_FLAGS.binary_search(&region).ok().map(|_| region)
+            }
+        }
+    }
+}
@@ -0,0 +1,108 @@
+//! Generation of enumerated properties backed by a UCD property file, e.g.
+//! `Indic_Syllabic_Category`.
+//!
+//! Each property becomes an enum with a `from_char` constructor. Codepoints that are not listed
+//! in the file resolve to the default of the `@missing` line covering them, or to the property's
+//! overall default if there is none.
+
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+
+use crate::{Ages, UnipropsBuilder, lookup::PropertyTable, ucd};
+
+/// Description of an enumerated property stored in a UCD property file.
+pub(crate) struct EnumeratedProperty<'a> {
+    /// Name of the generated enum.
+    pub name: &'a str,
+    /// Contents of the UCD property file.
+    pub source: &'a str,
+    /// Index of the field holding the value, not counting the codepoint field.
+    pub field: usize,
+    /// Value of all codepoints missing from the file.
+    pub default: &'a str,
+    /// Variant names for short value aliases, e.g. `("D", "Dual_Joining")`.
+    pub aliases: &'a [(&'a str, &'a str)],
+}
+
+impl EnumeratedProperty<'_> {
+    fn variant(&self, value: &str) -> Ident {
+        let value = self
+            .aliases
+            .iter()
+            .find(|(alias, _)| *alias == value)
+            .map_or(value, |(_, name)| name);
+        variant_ident(value)
+    }
+}
+
+/// Converts a property value alias such as `Vowel_Dependent` into an enum variant.
+pub(crate) fn variant_ident(value: &str) -> Ident {
+    let variant = value
+        .split(['_', ' ', '-'])
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect::<String>();
+    format_ident!("{}", variant)
+}
+
+impl UnipropsBuilder<'_> {
+    pub(crate) fn generate_enumerated(
+        &self,
+        property: &EnumeratedProperty,
+        ages: Option<&Ages>,
+    ) -> TokenStream {
+        let groups = ucd::to_groups_with_defaults(property.source, property.field)
+            .into_iter()
+            .filter(|g| g.value != property.default)
+            .collect::<Vec<_>>();
+        let groups = self.restrict_to_assigned(ages, groups);
+
+        let mut values = groups.iter().map(|g| g.value.as_str()).collect::<Vec<_>>();
+        values.push(property.default);
+        values.sort();
+        values.dedup();
+
+        let enum_ident = format_ident!("{}", property.name);
+        let variants = values.iter().map(|value| property.variant(value));
+        let default = property.variant(property.default);
+
+        let table = PropertyTable {
+            name: property.name,
+            value_type: quote! { #enum_ident },
+            value_size: 1,
+            option_size: 1,
+            groups: &groups,
+            value_tokens: &|value| {
+                let variant = property.variant(value);
+                quote! { #enum_ident::#variant }
+            },
+        };
+        let (statics, body) = table.generate(self.table_options());
+
+        quote! {
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            pub enum #enum_ident {
+                #(#variants),*
+            }
+
+            #statics
+
+            impl #enum_ident {
+                #[inline(always)]
+                fn lookup(c: char) -> ::std::option::Option<Self> {
+                    #body
+                }
+
+                #[inline(always)]
+                pub fn from_char(c: char) -> Self {
+                    Self::lookup(c).unwrap_or(Self::#default)
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,173 @@
+//! UAX #31 identifier validation on top of `XID_Start` and `XID_Continue` from
+//! `DerivedCoreProperties.txt`.
+//!
+//! `XID_*` is used instead of `ID_*` because it is closed under NFKC normalization, as
+//! recommended by UAX #31.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{
+    Ages, MappingGroup, UnicodeRecord, UnipropsBuilder, binary::DERIVED_CORE_PROPERTIES,
+    joining::DERIVED_JOINING_TYPE, lookup::PropertyTable, ucd,
+};
+
+/// `Canonical_Combining_Class=Virama`
+const VIRAMA: u8 = 9;
+
+impl UnipropsBuilder<'_> {
+    pub(crate) fn generate_identifiers(
+        &self,
+        records: &[UnicodeRecord],
+        ages: Option<&Ages>,
+    ) -> TokenStream {
+        let xid_start = self.generate_binary_property(
+            "XID_Start",
+            "is_xid_start",
+            quote! { pub },
+            &self.restrict_to_assigned(
+                ages,
+                ucd::binary_property_groups(DERIVED_CORE_PROPERTIES, "XID_Start"),
+            ),
+        );
+        let xid_continue = self.generate_binary_property(
+            "XID_Continue",
+            "is_xid_continue",
+            quote! { pub },
+            &self.restrict_to_assigned(
+                ages,
+                ucd::binary_property_groups(DERIVED_CORE_PROPERTIES, "XID_Continue"),
+            ),
+        );
+
+        let mut viramas: Vec<MappingGroup> = Vec::new();
+        for record in records
+            .iter()
+            .filter(|r| r.canonical_combining_class == VIRAMA)
+        {
+            match viramas.last_mut() {
+                Some(last) if last.end + 1 == record.code_point => last.end = record.code_point,
+                _ => viramas.push(MappingGroup {
+                    value: String::new(),
+                    start: record.code_point,
+                    end: record.code_point,
+                }),
+            }
+        }
+        let virama = self.generate_binary_property("Virama", "is_virama", quote! {}, &viramas);
+
+        // Only the joining types which take part in rule A1 are kept
+        let joining_groups = ucd::to_groups(&ucd::parse_property_file(DERIVED_JOINING_TYPE), 0)
+            .into_iter()
+            .filter(|g| ["D", "L", "R", "T"].contains(&g.value.as_str()))
+            .collect::<Vec<_>>();
+        let joining_groups = self.restrict_to_assigned(ages, joining_groups);
+        let table = PropertyTable {
+            name: "IdentifierJoiningType",
+            value_type: quote! { u8 },
+            value_size: 1,
+            option_size: 2,
+            groups: &joining_groups,
+            value_tokens: &|value| {
+                let value = value.as_bytes()[0];
+                quote! { #value }
+            },
+        };
+        let (joining_statics, joining_body) = table.generate(self.table_options());
+
+        quote! {
+            #xid_start
+            #xid_continue
+            #virama
+            #joining_statics
+
+            /// Returns the `Joining_Type` of `c` as an ASCII letter (`D`, `L`, `R` or `T`).
+            #[inline(always)]
+            fn identifier_joining_type(c: char) -> ::std::option::Option<u8> {
+                #joining_body
+            }
+
+            /// Checks the UAX #31 contexts in which ZWNJ and ZWJ are allowed: after a virama
+            /// (rules A2 and B), and ZWNJ between a left- and a right-joining character,
+            /// ignoring transparent ones (rule A1).
+            fn is_valid_joiner(c: char, before: &str, after: &str) -> bool {
+                if c != '\u{200C}' && c != '\u{200D}' {
+                    return false;
+                }
+                if before.chars().next_back().is_some_and(is_virama) {
+                    return true;
+                }
+
+                let not_transparent = |t: &::std::option::Option<u8>| *t != ::std::option::Option::Some(b'T');
+                let left = before.chars().rev().map(identifier_joining_type).find(not_transparent);
+                let right = after.chars().map(identifier_joining_type).find(not_transparent);
+                c == '\u{200C}'
+                    && matches!(left, ::std::option::Option::Some(::std::option::Option::Some(b'D' | b'L')))
+                    && matches!(right, ::std::option::Option::Some(::std::option::Option::Some(b'D' | b'R')))
+            }
+
+            /// A UAX #31 identifier profile: the default `XID_Start XID_Continue*` syntax plus
+            /// optional extra characters.
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            pub struct IdentifierProfile {
+                /// Characters allowed anywhere, including at the start, e.g. `_` or `$`.
+                pub extra_start: &'static [char],
+                /// Characters allowed anywhere but at the start, e.g. `'` in some languages.
+                pub extra_continue: &'static [char],
+                /// Characters allowed only in the middle of an identifier and never twice in a
+                /// row, e.g. `-` or `.`.
+                pub medial: &'static [char],
+                /// Whether ZWNJ and ZWJ are allowed in the contexts defined by UAX #31.
+                pub allow_joiners: bool,
+            }
+
+            impl IdentifierProfile {
+                /// The UAX #31 default identifier syntax without any extra characters.
+                pub const DEFAULT: Self = Self {
+                    extra_start: &[],
+                    extra_continue: &[],
+                    medial: &[],
+                    allow_joiners: false,
+                };
+
+                /// Returns `true` if `s` is a valid identifier under this profile.
+                pub fn is_identifier(&self, s: &str) -> bool {
+                    let mut chars = s.char_indices();
+                    let ::std::option::Option::Some((_, first)) = chars.next() else {
+                        return false;
+                    };
+                    if !is_xid_start(first) && !self.extra_start.contains(&first) {
+                        return false;
+                    }
+
+                    chars.all(|(i, c)| {
+                        let (before, after) = (&s[..i], &s[i + c.len_utf8()..]);
+                        // Checked first, since a medial such as U+00B7 may also be XID_Continue
+                        if self.medial.contains(&c) {
+                            return after
+                                .chars()
+                                .next()
+                                .is_some_and(|next| !self.medial.contains(&next));
+                        }
+                        is_xid_continue(c)
+                            || self.extra_start.contains(&c)
+                            || self.extra_continue.contains(&c)
+                            || (self.allow_joiners && is_valid_joiner(c, before, after))
+                    })
+                }
+            }
+
+            impl ::std::default::Default for IdentifierProfile {
+                fn default() -> Self {
+                    Self::DEFAULT
+                }
+            }
+
+            /// Returns `true` if `s` is a UAX #31 default identifier (`XID_Start XID_Continue*`).
+            #[inline]
+            pub fn is_identifier(s: &str) -> bool {
+                IdentifierProfile::DEFAULT.is_identifier(s)
+            }
+        }
+    }
+}
@@ -0,0 +1,38 @@
+//! Support for `IndicSyllabicCategory.txt` and `IndicPositionalCategory.txt`.
+
+use proc_macro2::TokenStream;
+
+use crate::{Ages, UnipropsBuilder, enumerated::EnumeratedProperty};
+
+pub(crate) const INDIC_SYLLABIC_CATEGORY: &str =
+    include_str!("../assets/IndicSyllabicCategory.txt");
+pub(crate) const INDIC_POSITIONAL_CATEGORY: &str =
+    include_str!("../assets/IndicPositionalCategory.txt");
+
+impl UnipropsBuilder<'_> {
+    pub(crate) fn generate_indic_syllabic_category(&self, ages: Option<&Ages>) -> TokenStream {
+        self.generate_enumerated(
+            &EnumeratedProperty {
+                name: "IndicSyllabicCategory",
+                source: INDIC_SYLLABIC_CATEGORY,
+                field: 0,
+                default: "Other",
+                aliases: &[],
+            },
+            ages,
+        )
+    }
+
+    pub(crate) fn generate_indic_positional_category(&self, ages: Option<&Ages>) -> TokenStream {
+        self.generate_enumerated(
+            &EnumeratedProperty {
+                name: "IndicPositionalCategory",
+                source: INDIC_POSITIONAL_CATEGORY,
+                field: 0,
+                default: "NA",
+                aliases: &[],
+            },
+            ages,
+        )
+    }
+}
@@ -0,0 +1,73 @@
+//! Support for `Joining_Type` and `Joining_Group` from `ArabicShaping.txt` and
+//! `DerivedJoiningType.txt`.
+
+use proc_macro2::TokenStream;
+
+use crate::{Ages, UnipropsBuilder, enumerated::EnumeratedProperty};
+
+pub(crate) const ARABIC_SHAPING: &str = include_str!("../assets/ArabicShaping.txt");
+pub(crate) const DERIVED_JOINING_TYPE: &str = include_str!("../assets/DerivedJoiningType.txt");
+
+const JOINING_TYPE_ALIASES: &[(&str, &str)] = &[
+    ("C", "Join_Causing"),
+    ("D", "Dual_Joining"),
+    ("L", "Left_Joining"),
+    ("R", "Right_Joining"),
+    ("T", "Transparent"),
+    ("U", "Non_Joining"),
+];
+
+/// `ArabicShaping.txt` spells joining groups in upper case with spaces (`TEH MARBUTA GOAL`),
+/// while everywhere else they use the `Teh_Marbuta_Goal` form.
+fn normalize_joining_group(value: &str) -> String {
+    value
+        .split([' ', '_'])
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_string() + &chars.as_str().to_ascii_lowercase(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+impl UnipropsBuilder<'_> {
+    pub(crate) fn generate_joining_type(&self, ages: Option<&Ages>) -> TokenStream {
+        self.generate_enumerated(
+            &EnumeratedProperty {
+                name: "JoiningType",
+                source: DERIVED_JOINING_TYPE,
+                field: 0,
+                default: "U",
+                aliases: JOINING_TYPE_ALIASES,
+            },
+            ages,
+        )
+    }
+
+    pub(crate) fn generate_joining_group(&self, ages: Option<&Ages>) -> TokenStream {
+        let source = ARABIC_SHAPING
+            .lines()
+            .map(|line| match line.rsplit_once(';') {
+                Some((head, group)) if !line.starts_with('#') => {
+                    format!("{head}; {}", normalize_joining_group(group.trim()))
+                }
+                _ => line.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        self.generate_enumerated(
+            &EnumeratedProperty {
+                name: "JoiningGroup",
+                source: &source,
+                field: 2,
+                default: "No_Joining_Group",
+                aliases: &[],
+            },
+            ages,
+        )
+    }
+}
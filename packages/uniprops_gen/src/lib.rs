@@ -7,9 +7,32 @@ use std::{
     env,
     fs::File,
     io::{BufReader, Write},
-    path::Path,
+    path::{Path, PathBuf},
+    sync::Once,
 };
 
+mod age;
+mod binary;
+mod case;
+mod collation;
+mod confusables;
+mod emoji;
+mod enumerated;
+mod identifier;
+mod indic;
+mod joining;
+mod lookup;
+mod normalization;
+mod script;
+mod security;
+mod ucd;
+mod unihan;
+mod variation;
+mod vertical;
+
+use age::Ages;
+use lookup::{PropertyTable, TableOptions};
+
 fn deserialize_hex_u32<'de, D>(deserializer: D) -> Result<u32, D::Error>
 where
     D: Deserializer<'de>,
@@ -52,6 +75,10 @@ pub struct UnicodeRecord {
 ///    codepoints (e.g., keeping only identifiers, or a specific script), **use `BSearch`**.
 ///    The resulting array will be tiny, and the binary search will comfortably sit in the L1 cache,
 ///    often outperforming `Trie` by avoiding dependent memory fetches.
+///
+/// Boolean properties (`is_xid_start`, `is_cased`, ...) pick their layout automatically: with the
+/// `Trie` strategies they are stored as a bitset trie of deduplicated `u64` words, one bit per
+/// codepoint, instead of one byte per codepoint.
 #[derive(Debug, Clone, Copy)]
 pub enum LookupStrategy {
     /// Generates a sorted array of contiguous codepoint ranges and performs a binary search (`O(log N)`).
@@ -70,6 +97,33 @@ pub enum LookupStrategy {
     /// - You are targeting memory-constrained environments (Wasm, embedded).
     BSearch,
 
+    /// Same data as [`BSearch`](Self::BSearch), but the range starts are stored in Eytzinger
+    /// (BFS) order in their own array, with the ends and values in separate arrays.
+    ///
+    /// **Performance Characteristics:**
+    /// The search is branchless (the comparison result is added to the index instead of being
+    /// branched on) and runs a constant number of steps, and the top levels of the implicit
+    /// tree share the first cache lines of the 4-byte `STARTS` array. `ENDS` and `VALUES` are
+    /// only read once, after the search.
+    ///
+    /// The standard library's binary search is branchless too, so the gain depends on the
+    /// target: on x86-64 with hot caches, the full `Category` table measured ~8.7 ns per lookup
+    /// versus ~6.9 ns for `BSearch` (`benches/lookup_strategy_benchmark.rs`). Benchmark on your
+    /// target before picking it over `BSearch`.
+    Eytzinger,
+
+    /// Generates a minimal perfect hash over the individual codepoints of the table (a
+    /// CHD/PtrHash-style table of `u16` pilots, with the keys and values stored in hash order).
+    ///
+    /// **Performance Characteristics:**
+    /// A lookup is two hashes and three reads (pilot, key, value) regardless of the table size,
+    /// and the table takes ~4.5 bytes per codepoint plus the value. Since every codepoint of a
+    /// range is a separate key, it only suits very sparse, scattered sets (a few hundred
+    /// codepoints such as a confusable list) that neither ranges nor trie blocks describe well.
+    /// Dense data such as the full categories would generate hundreds of thousands of keys, so
+    /// tables of more than 65536 codepoints fall back to `BSearch`.
+    PerfectHash,
+
     /// Generates a two-level pre-computed array (Trie) for `O(1)` constant-time lookups.
     ///
     /// This strategy chunks the codepoint space into blocks of size `2^shift`. It generates an
@@ -91,11 +145,60 @@ pub enum LookupStrategy {
     ///   suffers. A single valid codepoint in a block forces the allocation of 4,095 empty slots
     ///   (unless an identical block already exists).
     Trie { shift: u8 },
+
+    /// Generates a three-level Trie: the `INDICES` of [`Trie`](Self::Trie) are themselves split
+    /// into deduplicated `INDEX_CHUNKS`, like the tables of ICU and the Rust standard library.
+    ///
+    /// `cp >> shift1` selects a chunk of `2^(shift1 - shift2)` block indices, and the block of
+    /// `2^shift2` values is selected inside that chunk. Requires `shift2 < shift1 <= 20`.
+    ///
+    /// **Performance Characteristics:**
+    /// Lookups stay `O(1)` but need three dependent reads instead of two. In exchange, the
+    /// large runs of unassigned or private use planes collapse into a single shared chunk, so
+    /// small blocks (e.g. `shift2 = 5`) no longer blow up the index array. With
+    /// `Trie3 { shift1: 10, shift2: 5 }` the full category tables take ~22KB instead of the
+    /// ~46KB of `Trie { shift: 8 }`.
+    Trie3 { shift1: u8, shift2: u8 },
+
+    /// Measures `BSearch`, `Trie` with shifts 5 to 10 and several `Trie3` layouts for every
+    /// table, and generates the best one according to `objective`.
+    ///
+    /// **Cost Model:**
+    /// The size is the byte size of the generated statics. Since the generator does not know
+    /// the size of the value types, an `Option<T>` is assumed to take as many bytes as needed to
+    /// number the distinct values (exact for the generated enums). The probe cost is the number
+    /// of cache lines a lookup touches: one per trie level (plus one for the palette of packed
+    /// blocks), and `log2(lines) + 1` for a binary search over `lines` cache lines.
+    ///
+    /// Each table is measured separately, so a sparse table (e.g. `Virama`) may end up with
+    /// `BSearch` while the dense `Category` table of the same module uses a `Trie`.
+    Auto { objective: LookupObjective },
+}
+
+/// Leading table checked by `Category::from_char` before the general lookup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CategoryFastPath {
+    /// Every character goes through the configured [`LookupStrategy`].
+    #[default]
+    Disabled,
+    /// A direct `[Option<Category>; 128]` table for `U+0000..=U+007F`.
+    Ascii,
+    /// A direct `[Option<Category>; 256]` table for `U+0000..=U+00FF`.
+    Latin1,
+}
+
+/// What [`LookupStrategy::Auto`] optimizes for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LookupObjective {
+    /// The smallest tables, ties broken by the lowest probe cost.
+    Size,
+    /// The lowest probe cost, ties broken by the smallest tables.
+    Speed,
 }
 
 #[derive(Debug, Clone)]
-struct MappingGroup {
-    general_category: String,
+pub(crate) struct MappingGroup {
+    value: String,
     start: u32,
     end: u32,
 }
@@ -119,6 +222,73 @@ fn get_tag_by_name(name: &str) -> PositionTag {
     }
 }
 
+const UNICODE_DATA: &str = include_str!("../assets/UnicodeData.txt");
+
+/// Warns once per build script when the bundled UCD files come from different Unicode versions,
+/// or when `DerivedAge.txt` is older than `UnicodeData.txt`. Newer characters would have no age,
+/// so `age` returns `None` for them and `assigned_since` drops them from every table.
+fn warn_on_version_skew() {
+    static CHECKED: Once = Once::new();
+    CHECKED.call_once(|| {
+        let files = [
+            ("DerivedAge.txt", age::DERIVED_AGE),
+            ("DerivedCoreProperties.txt", binary::DERIVED_CORE_PROPERTIES),
+            (
+                "DerivedNormalizationProps.txt",
+                binary::DERIVED_NORMALIZATION_PROPS,
+            ),
+            ("CaseFolding.txt", case::CASE_FOLDING),
+            ("allkeys.txt", collation::ALLKEYS),
+            ("confusables.txt", confusables::CONFUSABLES),
+            ("IndicSyllabicCategory.txt", indic::INDIC_SYLLABIC_CATEGORY),
+            (
+                "IndicPositionalCategory.txt",
+                indic::INDIC_POSITIONAL_CATEGORY,
+            ),
+            ("ArabicShaping.txt", joining::ARABIC_SHAPING),
+            ("DerivedJoiningType.txt", joining::DERIVED_JOINING_TYPE),
+            ("Scripts.txt", script::SCRIPTS),
+            ("ScriptExtensions.txt", script::SCRIPT_EXTENSIONS),
+            ("PropertyValueAliases.txt", script::PROPERTY_VALUE_ALIASES),
+            ("IdentifierStatus.txt", security::IDENTIFIER_STATUS),
+            ("IdentifierType.txt", security::IDENTIFIER_TYPE),
+            (
+                "emoji-variation-sequences.txt",
+                variation::EMOJI_VARIATION_SEQUENCES,
+            ),
+            ("VerticalOrientation.txt", vertical::VERTICAL_ORIENTATION),
+        ];
+        let versions = files
+            .iter()
+            .filter_map(|&(name, src)| Some((name, ucd::file_version(src)?)))
+            .collect::<Vec<_>>();
+        if versions.windows(2).any(|pair| pair[0].1 != pair[1].1) {
+            let versions = versions
+                .iter()
+                .map(|(name, version)| format!("{name} {version}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            println!(
+                "cargo:warning=uniprops-gen: the bundled UCD files come from different Unicode \
+                 versions ({versions}), refresh them with update_metadata.sh"
+            );
+        }
+
+        let ages = Ages::load();
+        let unaged = UNICODE_DATA
+            .lines()
+            .filter_map(|line| line.split(';').next())
+            .filter(|cp| ages.of(ucd::parse_code_point(cp)).is_none())
+            .count();
+        if unaged > 0 {
+            println!(
+                "cargo:warning=uniprops-gen: {unaged} characters of UnicodeData.txt have no age \
+                 in DerivedAge.txt, so `age` and `assigned_since` treat them as unassigned"
+            );
+        }
+    });
+}
+
 type CustomGenerator<'a> = Box<dyn Fn(&[UnicodeRecord]) -> String + 'a>;
 
 /// A builder for generating a Rust source file containing Unicode property tables and lookups.
@@ -129,8 +299,33 @@ pub struct UnipropsBuilder<'a> {
     out_name: String,
     gen_categories: bool,
     gen_digits: bool,
+    gen_age: bool,
+    gen_indic_syllabic_category: bool,
+    gen_indic_positional_category: bool,
+    gen_joining_type: bool,
+    gen_joining_group: bool,
+    gen_vertical_orientation: bool,
+    unihan_dir: Option<PathBuf>,
+    gen_normalization: bool,
+    gen_confusables: bool,
+    gen_scripts: bool,
+    gen_identifier_security: bool,
+    gen_identifiers: bool,
+    gen_collation: bool,
+    gen_variation_sequences: bool,
+    gen_emoji_sequences: bool,
+    gen_flags: bool,
+    gen_case_orbits: bool,
+    gen_case_properties: bool,
+    gen_nfkc_casefold: bool,
     lookup_strategy: LookupStrategy,
+    packed_blocks: bool,
+    category_fast_path: CategoryFastPath,
+    gen_utf8_lookup: bool,
+    gen_batch_lookups: bool,
     filter: Box<dyn Fn(&UnicodeRecord) -> bool + 'a>,
+    assigned_since: Option<(u8, u8)>,
+    check_versions: bool,
     custom_generators: Vec<CustomGenerator<'a>>,
 }
 
@@ -144,8 +339,33 @@ impl<'a> UnipropsBuilder<'a> {
             out_name: "generated_uniprops.rs".to_string(),
             gen_categories: true,
             gen_digits: true,
+            gen_age: false,
+            gen_indic_syllabic_category: false,
+            gen_indic_positional_category: false,
+            gen_joining_type: false,
+            gen_joining_group: false,
+            gen_vertical_orientation: false,
+            unihan_dir: None,
+            gen_normalization: false,
+            gen_confusables: false,
+            gen_scripts: false,
+            gen_identifier_security: false,
+            gen_identifiers: false,
+            gen_collation: false,
+            gen_variation_sequences: false,
+            gen_emoji_sequences: false,
+            gen_flags: false,
+            gen_case_orbits: false,
+            gen_case_properties: false,
+            gen_nfkc_casefold: false,
             lookup_strategy: LookupStrategy::Trie { shift: 8 },
+            packed_blocks: false,
+            category_fast_path: CategoryFastPath::Disabled,
+            gen_utf8_lookup: false,
+            gen_batch_lookups: false,
             filter: Box::new(|_| true),
+            assigned_since: None,
+            check_versions: true,
             custom_generators: Default::default(),
         }
     }
@@ -158,7 +378,7 @@ impl<'a> UnipropsBuilder<'a> {
         self
     }
 
-    /// Overrides the internal lookup strategy for the generated category tables.
+    /// Overrides the internal lookup strategy for the generated category (and other property) tables.
     ///
     /// By default, the builder uses `LookupStrategy::Trie { shift: 8 }` which is optimized for
     /// the full Unicode dataset.
@@ -166,11 +386,60 @@ impl<'a> UnipropsBuilder<'a> {
     /// You should override this to `LookupStrategy::BSearch` if you are applying an aggressive
     /// `.filter()` closure that discards the majority of codepoints. In such scenarios, `BSearch`
     /// dramatically shrinks the compiled binary size and often executes faster due to L1 cache locality.
+    ///
+    /// Alternatively, `LookupStrategy::Auto` measures the candidate layouts of every table and
+    /// picks the smallest or fastest one.
     pub fn with_lookup_strategy(mut self, lookup_strategy: LookupStrategy) -> Self {
         self.lookup_strategy = lookup_strategy;
         self
     }
 
+    /// Toggles bit-packing of the `BLOCKS` arrays generated by the `Trie` strategies.
+    ///
+    /// Instead of one `Option<T>` per codepoint, every value is stored as a code of the minimal
+    /// width (5 bits for the 29 categories plus `None`) and decoded with a shift and a mask. For
+    /// the full `Category` table with `Trie { shift: 8 }`, this shrinks `CATEGORY_BLOCKS` from
+    /// 41,472 to 25,921 bytes, at the cost of an extra table read and a few instructions per
+    /// lookup. Tables with more than 511 distinct values are never packed.
+    pub fn with_packed_blocks(mut self, enable: bool) -> Self {
+        self.packed_blocks = enable;
+        self
+    }
+
+    /// Adds a direct table for ASCII or Latin-1 in front of the `Category::from_char` lookup.
+    ///
+    /// A single indexed read replaces the two dependent reads of a `Trie` (or the binary search
+    /// of `BSearch`) for the most common characters, at the cost of 128 or 256 bytes and one
+    /// extra comparison for the other characters. Worth it when most of the input is ASCII.
+    pub fn with_category_fast_path(mut self, fast_path: CategoryFastPath) -> Self {
+        self.category_fast_path = fast_path;
+        self
+    }
+
+    /// Toggles the generation of `Category::from_utf8(bytes)`, which returns the category of the
+    /// character at the start of `bytes` and the length of its UTF-8 sequence.
+    ///
+    /// It walks a separate trie whose levels are indexed by the UTF-8 bytes themselves, so the
+    /// input is validated and looked up without decoding it into a `char` first. The trie always
+    /// uses 64-entry blocks regardless of the [`LookupStrategy`], and honors
+    /// [`with_packed_blocks`](Self::with_packed_blocks).
+    pub fn with_utf8_lookup(mut self, enable: bool) -> Self {
+        self.gen_utf8_lookup = enable;
+        self
+    }
+
+    /// Toggles the generation of batch lookups over whole buffers: `Category::classify_str(s, out)`
+    /// (with categories) and `get_digit_values(chars, out)` (with digits).
+    ///
+    /// Both load 16 characters at a time into `u8` lanes with plain array operations (no
+    /// target-specific intrinsics) that the compiler can vectorize. A run of 16 ASCII characters
+    /// is answered lane by lane, categories from a 128-entry table and digits from a bit mask and
+    /// a subtraction. Other runs fall back to the scalar lookups.
+    pub fn with_batch_lookups(mut self, enable: bool) -> Self {
+        self.gen_batch_lookups = enable;
+        self
+    }
+
     /// Toggles the generation of the `Category` enum and character-to-category mapping.
     pub fn with_categories(mut self, enable: bool) -> Self {
         self.gen_categories = enable;
@@ -183,6 +452,195 @@ impl<'a> UnipropsBuilder<'a> {
         self
     }
 
+    /// Toggles the generation of the `age` function and the `UnicodeVersion` struct.
+    ///
+    /// `age(c)` returns the version of Unicode in which `c` was first assigned, as listed in
+    /// `DerivedAge.txt`. The table uses the configured [`LookupStrategy`].
+    pub fn with_age(mut self, enable: bool) -> Self {
+        self.gen_age = enable;
+        self
+    }
+
+    /// Toggles the generation of the `IndicSyllabicCategory` enum and its `from_char` lookup.
+    ///
+    /// Codepoints not listed in `IndicSyllabicCategory.txt` map to `IndicSyllabicCategory::Other`.
+    pub fn with_indic_syllabic_category(mut self, enable: bool) -> Self {
+        self.gen_indic_syllabic_category = enable;
+        self
+    }
+
+    /// Toggles the generation of the `IndicPositionalCategory` enum and its `from_char` lookup.
+    ///
+    /// Codepoints not listed in `IndicPositionalCategory.txt` map to `IndicPositionalCategory::NA`.
+    pub fn with_indic_positional_category(mut self, enable: bool) -> Self {
+        self.gen_indic_positional_category = enable;
+        self
+    }
+
+    /// Toggles the generation of the `JoiningType` enum and its `from_char` lookup.
+    ///
+    /// Values come from `DerivedJoiningType.txt`, so characters not listed in `ArabicShaping.txt`
+    /// get their derived type (`Transparent` for most combining marks, `NonJoining` otherwise).
+    pub fn with_joining_type(mut self, enable: bool) -> Self {
+        self.gen_joining_type = enable;
+        self
+    }
+
+    /// Toggles the generation of the `JoiningGroup` enum and its `from_char` lookup.
+    ///
+    /// Codepoints not listed in `ArabicShaping.txt` map to `JoiningGroup::NoJoiningGroup`.
+    pub fn with_joining_group(mut self, enable: bool) -> Self {
+        self.gen_joining_group = enable;
+        self
+    }
+
+    /// Toggles the generation of the `VerticalOrientation` enum and its `from_char` lookup.
+    ///
+    /// `VerticalOrientation::from_char(c)` returns one of `U`, `R`, `Tu` or `Tr`. Unassigned
+    /// codepoints get the default of the range they belong to, e.g. `U` inside CJK blocks.
+    pub fn with_vertical_orientation(mut self, enable: bool) -> Self {
+        self.gen_vertical_orientation = enable;
+        self
+    }
+
+    /// Enables the generation of Unihan lookups from the files in `dir`.
+    ///
+    /// Unihan is not bundled with this crate because of its size. Download `Unihan.zip` from
+    /// <https://www.unicode.org/Public/UCD/latest/ucd/> and extract `Unihan_IRGSources.txt`,
+    /// `Unihan_NumericValues.txt` and `Unihan_Readings.txt` into `dir`. Relative paths are
+    /// resolved against the package root, which is the working directory of `build.rs`.
+    ///
+    /// Generates `k_rs_unicode`, `k_total_strokes`, `k_mandarin`, `k_primary_numeric`,
+    /// `k_accounting_numeric` and `k_other_numeric`. Each returns the first value listed for the
+    /// character, e.g. the zh-Hans stroke count for `k_total_strokes`.
+    ///
+    /// Cargo does not track these files, so add a `cargo:rerun-if-changed` line for `dir` to
+    /// your `build.rs` if they may change.
+    pub fn with_unihan<P: AsRef<Path>>(mut self, dir: P) -> Self {
+        self.unihan_dir = Some(dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Toggles the generation of `nfd`, `canonical_decomposition` and `canonical_combining_class`.
+    ///
+    /// The normalization tables are built from all records regardless of `.filter()`, since a
+    /// partial decomposition table would produce incorrect results.
+    pub fn with_normalization(mut self, enable: bool) -> Self {
+        self.gen_normalization = enable;
+        self
+    }
+
+    /// Toggles the generation of UTS #39 confusable detection: `confusable_prototype`,
+    /// `skeleton` and `is_confusable`.
+    ///
+    /// The skeleton is computed on top of `nfd`, so this also enables
+    /// [`with_normalization`](Self::with_normalization). Only characters accepted by `.filter()`
+    /// are mapped to their prototypes.
+    pub fn with_confusables(mut self, enable: bool) -> Self {
+        self.gen_confusables = enable;
+        self
+    }
+
+    /// Toggles the generation of the `Script` enum and the `script_extensions` function.
+    ///
+    /// `Script::from_char(c)` returns the `Script` property from `Scripts.txt`, while
+    /// `script_extensions(c)` returns the scripts `c` is commonly used with, from
+    /// `ScriptExtensions.txt`.
+    pub fn with_scripts(mut self, enable: bool) -> Self {
+        self.gen_scripts = enable;
+        self
+    }
+
+    /// Toggles the generation of the UTS #39 identifier profile: `identifier_status`,
+    /// `identifier_type` and `restriction_level`.
+    ///
+    /// `restriction_level` is computed from `Script_Extensions`, so this also enables
+    /// [`with_scripts`](Self::with_scripts).
+    pub fn with_identifier_security(mut self, enable: bool) -> Self {
+        self.gen_identifier_security = enable;
+        self
+    }
+
+    /// Toggles the generation of UAX #31 identifier validation: `is_xid_start`,
+    /// `is_xid_continue`, `is_identifier` and the `IdentifierProfile` struct.
+    ///
+    /// `IdentifierProfile` extends the default syntax with extra start, continue and medial
+    /// characters (e.g. `$` or `-`) and the ZWNJ/ZWJ rules of UAX #31. Like the other
+    /// supplementary properties, the tables ignore `.filter()`.
+    pub fn with_identifiers(mut self, enable: bool) -> Self {
+        self.gen_identifiers = enable;
+        self
+    }
+
+    /// Toggles the generation of the Unicode Collation Algorithm with the default table from
+    /// `allkeys.txt`: `compare`, `sort_key` and their `_with_strength` variants.
+    ///
+    /// Strings are compared after canonical decomposition, so this also enables
+    /// [`with_normalization`](Self::with_normalization). The collation ignores `.filter()`;
+    /// characters excluded by `assigned_since` get implicit weights, like unassigned ones.
+    pub fn with_collation(mut self, enable: bool) -> Self {
+        self.gen_collation = enable;
+        self
+    }
+
+    /// Toggles the generation of `is_valid_variation_sequence` and `variation_description`
+    /// from `StandardizedVariants.txt` and `emoji-variation-sequences.txt`.
+    pub fn with_variation_sequences(mut self, enable: bool) -> Self {
+        self.gen_variation_sequences = enable;
+        self
+    }
+
+    /// Toggles the generation of `emoji_sequences`, which finds the RGI emoji sequences
+    /// (basic, keycap, flag, tag, modifier and ZWJ) listed in `emoji-sequences.txt` and
+    /// `emoji-zwj-sequences.txt`.
+    pub fn with_emoji_sequences(mut self, enable: bool) -> Self {
+        self.gen_emoji_sequences = enable;
+        self
+    }
+
+    /// Toggles the generation of `flag_country_code` and `is_regional_indicator`.
+    ///
+    /// `flag_country_code` decodes a pair of regional indicator symbols into its ISO 3166 region
+    /// code, accepting only the `RGI_Emoji_Flag_Sequence`s from `emoji-sequences.txt`.
+    pub fn with_flags(mut self, enable: bool) -> Self {
+        self.gen_flags = enable;
+        self
+    }
+
+    /// Toggles the generation of `case_orbit`, which returns every character matching a given
+    /// one case-insensitively (e.g. `k`, `K` and KELVIN SIGN).
+    ///
+    /// Orbits are the classes of characters sharing a simple case folding (`C` and `S` entries of
+    /// `CaseFolding.txt`), so the Turkic `İ` and `ı` stay out of the orbit of `i`. Characters
+    /// rejected by `.filter()` are left out.
+    pub fn with_case_orbits(mut self, enable: bool) -> Self {
+        self.gen_case_orbits = enable;
+        self
+    }
+
+    /// Toggles the generation of the derived case properties: `is_cased`, `is_case_ignorable`
+    /// and `changes_when_{lowercased, uppercased, titlecased, casefolded, casemapped,
+    /// nfkc_casefolded}`.
+    ///
+    /// They allow skipping case conversions in fast paths, e.g.
+    /// `s.chars().any(changes_when_lowercased)` tells whether `s.to_lowercase()` would allocate
+    /// a different string.
+    pub fn with_case_properties(mut self, enable: bool) -> Self {
+        self.gen_case_properties = enable;
+        self
+    }
+
+    /// Toggles the generation of `nfkc_casefold`, the `toNFKC_Casefold` operation recommended
+    /// by UAX #31 for case-insensitive identifier comparison.
+    ///
+    /// The result is normalized to NFC on top of `nfd`, so this also enables
+    /// [`with_normalization`](Self::with_normalization). Like the normalization tables, the
+    /// mapping ignores `.filter()`.
+    pub fn with_nfkc_casefold(mut self, enable: bool) -> Self {
+        self.gen_nfkc_casefold = enable;
+        self
+    }
+
     /// Registers a custom code generator function.
     ///
     /// Allows injecting custom properties extracted from `UnicodeData.txt` into the generated module
@@ -218,6 +676,30 @@ impl<'a> UnipropsBuilder<'a> {
         self
     }
 
+    /// Restricts every generated table to characters assigned in Unicode `major.minor` or earlier.
+    ///
+    /// This is applied in addition to the `.filter()` closure and is useful to reject characters
+    /// newer than what downstream consumers support.
+    ///
+    /// # Example
+    /// ```ignore
+    /// // Only characters that already existed in Unicode 6.0
+    /// builder.assigned_since(6, 0)
+    /// ```
+    pub fn assigned_since(mut self, major: u8, minor: u8) -> Self {
+        self.assigned_since = Some((major, minor));
+        self
+    }
+
+    /// Toggles a `cargo:warning` when the bundled UCD files come from different Unicode versions,
+    /// or when `DerivedAge.txt` does not cover every character of `UnicodeData.txt`.
+    ///
+    /// Enabled by default.
+    pub fn with_version_check(mut self, enable: bool) -> Self {
+        self.check_versions = enable;
+        self
+    }
+
     /// Executes the code generation process.
     ///
     /// This method:
@@ -229,13 +711,19 @@ impl<'a> UnipropsBuilder<'a> {
     /// # Panics
     /// Panics if `OUT_DIR` is not set, or if there are errors reading the asset or writing the output file.
     pub fn build(self) {
-        let raw_data = self.parse_data();
+        if self.check_versions {
+            warn_on_version_skew();
+        }
+        let ages = (self.gen_age || self.assigned_since.is_some()).then(Ages::load);
+        let records = self.parse_data(ages.as_ref());
+        let raw_data = records
+            .iter()
+            .filter(|r| (self.filter)(r))
+            .cloned()
+            .collect::<Vec<_>>();
 
         let categories = if self.gen_categories {
-            match self.lookup_strategy {
-                LookupStrategy::BSearch => self.generate_bsearch_impl(&raw_data),
-                LookupStrategy::Trie { shift } => self.generate_trie_impl(shift, &raw_data),
-            }
+            self.generate_categories(&raw_data)
         } else {
             quote! {}
         };
@@ -246,6 +734,122 @@ impl<'a> UnipropsBuilder<'a> {
             quote! {}
         };
 
+        let age = match &ages {
+            Some(ages) if self.gen_age => self.generate_age(ages),
+            _ => quote! {},
+        };
+
+        let indic_syllabic_category = if self.gen_indic_syllabic_category {
+            self.generate_indic_syllabic_category(ages.as_ref())
+        } else {
+            quote! {}
+        };
+
+        let indic_positional_category = if self.gen_indic_positional_category {
+            self.generate_indic_positional_category(ages.as_ref())
+        } else {
+            quote! {}
+        };
+
+        let joining_type = if self.gen_joining_type {
+            self.generate_joining_type(ages.as_ref())
+        } else {
+            quote! {}
+        };
+
+        let joining_group = if self.gen_joining_group {
+            self.generate_joining_group(ages.as_ref())
+        } else {
+            quote! {}
+        };
+
+        let vertical_orientation = if self.gen_vertical_orientation {
+            self.generate_vertical_orientation(ages.as_ref())
+        } else {
+            quote! {}
+        };
+
+        let unihan = match &self.unihan_dir {
+            Some(dir) => self.generate_unihan(dir, ages.as_ref()),
+            None => quote! {},
+        };
+
+        let normalization = if self.gen_normalization
+            || self.gen_confusables
+            || self.gen_collation
+            || self.gen_nfkc_casefold
+        {
+            self.generate_normalization(&records)
+        } else {
+            quote! {}
+        };
+
+        let confusables = if self.gen_confusables {
+            self.generate_confusables(&Self::get_mapping_groups(&raw_data))
+        } else {
+            quote! {}
+        };
+
+        let scripts = if self.gen_scripts || self.gen_identifier_security {
+            self.generate_scripts(ages.as_ref())
+        } else {
+            quote! {}
+        };
+
+        let identifier_security = if self.gen_identifier_security {
+            self.generate_identifier_security(ages.as_ref())
+        } else {
+            quote! {}
+        };
+
+        let identifiers = if self.gen_identifiers {
+            self.generate_identifiers(&records, ages.as_ref())
+        } else {
+            quote! {}
+        };
+
+        let collation = if self.gen_collation {
+            self.generate_collation(&records, ages.as_ref())
+        } else {
+            quote! {}
+        };
+
+        let variation_sequences = if self.gen_variation_sequences {
+            self.generate_variation_sequences(ages.as_ref())
+        } else {
+            quote! {}
+        };
+
+        let emoji_sequences = if self.gen_emoji_sequences {
+            self.generate_emoji_sequences(&records, ages.as_ref())
+        } else {
+            quote! {}
+        };
+
+        let flags = if self.gen_flags {
+            self.generate_flags(&records, ages.as_ref())
+        } else {
+            quote! {}
+        };
+
+        let case_orbits = if self.gen_case_orbits {
+            self.generate_case_orbits(&raw_data)
+        } else {
+            quote! {}
+        };
+
+        let case_properties = if self.gen_case_properties {
+            self.generate_case_properties(ages.as_ref())
+        } else {
+            quote! {}
+        };
+
+        let nfkc_casefold = if self.gen_nfkc_casefold {
+            self.generate_nfkc_casefold(&records, ages.as_ref())
+        } else {
+            quote! {}
+        };
+
         let mut custom_tokens = proc_macro2::TokenStream::new();
 
         for generator in self.custom_generators {
@@ -265,6 +869,25 @@ impl<'a> UnipropsBuilder<'a> {
             pub mod uniprops {
                 #categories
                 #digits
+                #age
+                #indic_syllabic_category
+                #indic_positional_category
+                #joining_type
+                #joining_group
+                #vertical_orientation
+                #unihan
+                #normalization
+                #confusables
+                #scripts
+                #identifier_security
+                #identifiers
+                #collation
+                #variation_sequences
+                #emoji_sequences
+                #flags
+                #case_orbits
+                #case_properties
+                #nfkc_casefold
                 #custom_tokens
             }
         };
@@ -277,31 +900,97 @@ impl<'a> UnipropsBuilder<'a> {
             .expect("Failed to write to output file");
     }
 
-    fn parse_data(&self) -> Vec<UnicodeRecord> {
-        let reader = BufReader::new(include_str!("../assets/UnicodeData.txt").as_bytes());
+    fn parse_data(&self, ages: Option<&Ages>) -> Vec<UnicodeRecord> {
+        let reader = BufReader::new(UNICODE_DATA.as_bytes());
         let mut parser = csv::ReaderBuilder::new()
             .has_headers(false)
             .delimiter(b';')
             .from_reader(reader);
 
         let mut raw_data = Vec::new();
+        let mut range_first: Option<UnicodeRecord> = None;
         for result in parser.deserialize::<UnicodeRecord>() {
             let record = result.expect("CSV Parse Error");
-            if (self.filter)(&record) {
-                raw_data.push(record);
+            match get_tag_by_name(&record.name) {
+                PositionTag::First => range_first = Some(record),
+                PositionTag::Last => {
+                    let first = range_first.take().expect("Range without a First record");
+                    self.push_assigned_range(ages, first, record, &mut raw_data);
+                }
+                PositionTag::None => {
+                    if self.is_assigned(ages, record.code_point) {
+                        raw_data.push(record);
+                    }
+                }
             }
         }
         raw_data.sort_by_key(|r| r.code_point);
         raw_data
     }
 
+    /// Pushes the `First`/`Last` records of the assigned parts of a range, clipped like the
+    /// other properties, so that `assigned_since` can split a range without collapsing it.
+    ///
+    /// A part of a single codepoint is pushed as a lone `First` record.
+    fn push_assigned_range(
+        &self,
+        ages: Option<&Ages>,
+        first: UnicodeRecord,
+        last: UnicodeRecord,
+        raw_data: &mut Vec<UnicodeRecord>,
+    ) {
+        let range = MappingGroup {
+            value: String::new(),
+            start: first.code_point,
+            end: last.code_point,
+        };
+        for part in self.restrict_to_assigned(ages, vec![range]) {
+            raw_data.push(UnicodeRecord {
+                code_point: part.start,
+                ..first.clone()
+            });
+            if part.end > part.start {
+                raw_data.push(UnicodeRecord {
+                    code_point: part.end,
+                    ..last.clone()
+                });
+            }
+        }
+    }
+
+    fn table_options(&self) -> TableOptions {
+        TableOptions {
+            strategy: self.lookup_strategy,
+            packed_blocks: self.packed_blocks,
+        }
+    }
+
+    fn is_assigned(&self, ages: Option<&Ages>, cp: u32) -> bool {
+        match (self.assigned_since, ages) {
+            (Some(version), Some(ages)) => ages.of(cp).is_some_and(|age| age <= version),
+            _ => true,
+        }
+    }
+
+    /// Clips supplementary property groups according to `assigned_since`.
+    fn restrict_to_assigned(
+        &self,
+        ages: Option<&Ages>,
+        groups: Vec<MappingGroup>,
+    ) -> Vec<MappingGroup> {
+        match (self.assigned_since, ages) {
+            (Some(version), Some(ages)) => ages.restrict(&groups, version),
+            _ => groups,
+        }
+    }
+
     fn get_mapping_groups(raw_data: &[UnicodeRecord]) -> Vec<MappingGroup> {
         let mut mapping_groups = Vec::new();
 
         if !raw_data.is_empty() {
             let record = &raw_data[0];
             let mut current_group = MappingGroup {
-                general_category: record.general_category.clone(),
+                value: record.general_category.clone(),
                 start: record.code_point,
                 end: record.code_point,
             };
@@ -309,14 +998,14 @@ impl<'a> UnipropsBuilder<'a> {
             for record in raw_data.iter().skip(1) {
                 let was_groupped = get_tag_by_name(&record.name) == PositionTag::Last;
                 if (record.code_point == current_group.end + 1
-                    && record.general_category == current_group.general_category)
+                    && record.general_category == current_group.value)
                     || was_groupped
                 {
                     current_group.end = record.code_point;
                 } else {
                     mapping_groups.push(current_group);
                     current_group = MappingGroup {
-                        general_category: record.general_category.clone(),
+                        value: record.general_category.clone(),
                         start: record.code_point,
                         end: record.code_point,
                     };
@@ -331,7 +1020,7 @@ impl<'a> UnipropsBuilder<'a> {
     fn get_unique_categories_sorted(mapping_groups: &[MappingGroup]) -> Vec<String> {
         let mut categories = mapping_groups
             .iter()
-            .map(|g| g.general_category.clone())
+            .map(|g| g.value.clone())
             .collect::<HashSet<_>>()
             .into_iter()
             .collect::<Vec<_>>();
@@ -354,164 +1043,153 @@ impl<'a> UnipropsBuilder<'a> {
         }
     }
 
-    fn generate_bsearch_impl(&self, raw_data: &[UnicodeRecord]) -> TokenStream {
-        let mut mapping_groups = Self::get_mapping_groups(raw_data);
+    fn generate_categories(&self, raw_data: &[UnicodeRecord]) -> TokenStream {
+        let mapping_groups = Self::get_mapping_groups(raw_data);
         let unique_categories = Self::get_unique_categories_sorted(&mapping_groups);
         let category_enum = Self::generate_category_enum(&unique_categories);
 
-        mapping_groups.sort_by(|a, b| a.start.cmp(&b.start));
+        let table = PropertyTable {
+            name: "Category",
+            value_type: quote! { Category },
+            value_size: 1,
+            option_size: 1,
+            groups: &mapping_groups,
+            value_tokens: &|value| {
+                let ident = format_ident!("{}", value);
+                quote! { Category::#ident }
+            },
+        };
+        let (statics, body) = table.generate(self.table_options());
 
-        let mapping_group_lookup = mapping_groups
-            .into_iter()
-            .map(|group| {
-                let enum_variant = format_ident!("{}", group.general_category);
-                let (start, end) = (group.start, group.end);
+        let fast_path_len: u32 = match self.category_fast_path {
+            CategoryFastPath::Disabled => 0,
+            CategoryFastPath::Ascii => 0x80,
+            CategoryFastPath::Latin1 => 0x100,
+        };
+        // Direct table of the first `len` categories
+        let direct_values = |len: u32| {
+            (0..len).map(
+                |cp| match mapping_groups.iter().find(|g| g.start <= cp && cp <= g.end) {
+                    Some(group) => {
+                        let ident = format_ident!("{}", group.value);
+                        quote! { Some(Category::#ident) }
+                    }
+                    None => quote! { None },
+                },
+            )
+        };
 
-                quote! {
-                    CategoryBounds { start: #start, end: #end, category: Category::#enum_variant }
+        let (fast_path_statics, fast_path) = if fast_path_len > 0 {
+            let values = direct_values(fast_path_len);
+            let len = fast_path_len as usize;
+            let statics = quote! {
+                static CATEGORY_FAST_PATH: [Option<Category>; #len] = [ #(#values),* ];
+            };
+            let fast_path = quote! {
+                if (c as u32) < #fast_path_len {
+                    return CATEGORY_FAST_PATH[c as usize];
                 }
-            })
-            .collect::<Vec<_>>();
-
-        let len = mapping_group_lookup.len();
-
-        quote! {
-            #category_enum
-
-            struct CategoryBounds {
-                start: u32,
-                end: u32,
-                category: Category,
-            }
-
-            static CATEGORY_LOOKUP: [CategoryBounds; #len] = [
-                #(#mapping_group_lookup),*
-            ];
-
-            impl Category {
-                #[inline(always)]
-                pub fn from_char(c: char) -> ::std::option::Option<Self> {
-                    CATEGORY_LOOKUP.binary_search_by(| g | {
-                        let code_point = c as u32;
+            };
+            (statics, fast_path)
+        } else {
+            (quote! {}, quote! {})
+        };
 
-                        if code_point < g.start {
-                            ::core::cmp::Ordering::Greater
-                        } else if code_point > g.end {
-                            ::core::cmp::Ordering::Less
-                        } else {
-                            ::core::cmp::Ordering::Equal
-                        }
-                    })
-                    .ok()
-                    .map(| i |
-                        // SAFETY: We found an element with index i just now, it MUST be in array
-                        unsafe { CATEGORY_LOOKUP.get_unchecked(i) }.category
-                    )
+        let (utf8_statics, utf8_fn) = if self.gen_utf8_lookup {
+            let table = PropertyTable {
+                name: "CategoryUtf8",
+                ..table
+            };
+            let (statics, body) = table.generate_utf8(self.packed_blocks);
+            let utf8_fn = quote! {
+                /// Returns the category of the character at the start of `bytes` and the length
+                /// of its UTF-8 sequence.
+                ///
+                /// Returns `None` if `bytes` does not start with a valid UTF-8 sequence, or if
+                /// the character has no category.
+                #[inline]
+                pub fn from_utf8(bytes: &[u8]) -> ::std::option::Option<(Self, usize)> {
+                    #body
                 }
-            }
-        }
-    }
+            };
+            (statics, utf8_fn)
+        } else {
+            (quote! {}, quote! {})
+        };
 
-    fn generate_trie_impl(&self, shift: u8, raw_data: &[UnicodeRecord]) -> TokenStream {
-        let size: u32 = 1 << (shift as u32);
-        let mask: u32 = size - 1;
-        let mapping_groups = Self::get_mapping_groups(raw_data);
-        let unique_categories = Self::get_unique_categories_sorted(&mapping_groups);
-        let category_enum = Self::generate_category_enum(&unique_categories);
+        let (batch_statics, batch_fn) = if self.gen_batch_lookups {
+            let values = direct_values(0x80);
+            let statics = quote! {
+                static CATEGORY_ASCII: [Option<Category>; 128] = [ #(#values),* ];
+            };
+            let batch_fn = quote! {
+                /// Writes the category of each character of `s` into `out`, in order, and
+                /// returns the number of characters written.
+                ///
+                /// Stops early if `out` is shorter than the number of characters in `s`.
+                pub fn classify_str(s: &str, out: &mut [::std::option::Option<Self>]) -> usize {
+                    let mut rest = s;
+                    let mut written = 0;
+                    // Bytes looked up one character at a time before checking for ASCII again,
+                    // doubled after every non-ASCII window so that mixed text rarely checks
+                    let mut window = 16;
+
+                    while written < out.len() && !rest.is_empty() {
+                        // The next 16 bytes as `u8` lanes are 16 ASCII characters if no lane has
+                        // its high bit set
+                        if let (Some(lanes), Some(dst)) = (
+                            rest.as_bytes().first_chunk::<16>(),
+                            out.get_mut(written..written + 16),
+                        ) {
+                            if lanes.iter().fold(0, |acc, &lane| acc | lane) < 0x80 {
+                                for (dst, &lane) in dst.iter_mut().zip(lanes) {
+                                    *dst = CATEGORY_ASCII[(lane & 0x7F) as usize];
+                                }
+                                rest = &rest[16..];
+                                written += 16;
+                                window = 16;
+                                continue;
+                            }
+                        }
 
-        let max_codepoint: u32 = 0x10FFFF;
-        let mut unique_blocks: Vec<Vec<Option<String>>> = Vec::new();
-        let mut indices: Vec<usize> = Vec::new();
-        let mut group_iter = mapping_groups.iter();
-        let mut current_group = group_iter.next();
-
-        for chunk_start in (0..=max_codepoint).step_by(size as usize) {
-            let mut block = Vec::with_capacity(size as usize);
-
-            for i in 0..size {
-                let cp = chunk_start + i;
-                while let Some(g) = current_group {
-                    if cp > g.end {
-                        current_group = group_iter.next();
-                    } else {
-                        break;
+                        let stop = rest.len().saturating_sub(window);
+                        window = (window * 2).min(256);
+                        let mut chars = rest.chars();
+                        while let ::std::option::Option::Some(c) = chars.next() {
+                            out[written] = Self::from_char(c);
+                            written += 1;
+                            if chars.as_str().len() <= stop || written == out.len() {
+                                break;
+                            }
+                        }
+                        rest = chars.as_str();
                     }
+                    written
                 }
-
-                let category = if let Some(g) = current_group {
-                    if cp >= g.start && cp <= g.end {
-                        Some(g.general_category.clone())
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                };
-                block.push(category);
-            }
-
-            if let Some(idx) = unique_blocks.iter().position(|b| b == &block) {
-                indices.push(idx);
-            } else {
-                indices.push(unique_blocks.len());
-                unique_blocks.push(block);
-            }
-        }
-
-        let index_type = if unique_blocks.len() <= (u8::MAX as usize) + 1 {
-            quote! { u8 }
-        } else if unique_blocks.len() <= (u16::MAX as usize) + 1 {
-            quote! { u16 }
+            };
+            (statics, batch_fn)
         } else {
-            quote! { compile_error!("Shift is too small, u16 overflow") }
+            (quote! {}, quote! {})
         };
-        let indices_tokens = indices.iter().map(|&idx| {
-            if unique_blocks.len() <= 256 {
-                let val = idx as u8;
-                quote! { #val }
-            } else {
-                let val = idx as u16;
-                quote! { #val }
-            }
-        });
-
-        let indices_len = indices.len();
-        let blocks_tokens = unique_blocks.iter().flatten().map(|opt_cat| match opt_cat {
-            Some(cat) => {
-                let ident = format_ident!("{}", cat);
-                quote! { Some(Category::#ident) }
-            }
-            None => quote! { None },
-        });
-        let blocks_len = unique_blocks.len() * (size as usize);
 
         quote! {
             #category_enum
 
-            static CATEGORY_INDICES:[#index_type; #indices_len] = [
-                #(#indices_tokens),*
-            ];
-
-            static CATEGORY_BLOCKS: [Option<Category>; #blocks_len] =[
-                #(#blocks_tokens),*
-            ];
+            #statics
+            #fast_path_statics
+            #utf8_statics
+            #batch_statics
 
             impl Category {
                 #[inline(always)]
                 pub fn from_char(c: char) -> ::std::option::Option<Self> {
-                    let cp = c as u32;
-                    if cp > #max_codepoint { return None; }
+                    #fast_path
+                    #body
+                }
 
-                    let index_idx = (cp >> #shift) as usize;
+                #utf8_fn
 
-                    // SAFETY: Arrays are generated to cover up to 0x10FFFF
-                    unsafe {
-                        let block_idx = *CATEGORY_INDICES.get_unchecked(index_idx) as usize;
-                        let offset = (cp & #mask) as usize;
-                        let final_pos = (block_idx << #shift) + offset;
-                        *CATEGORY_BLOCKS.get_unchecked(final_pos)
-                    }
-                }
+                #batch_fn
             }
         }
     }
@@ -569,6 +1247,61 @@ impl<'a> UnipropsBuilder<'a> {
             quote! {}
         };
 
+        let batch = if self.gen_batch_lookups {
+            // Bit `v` is set if the filter kept the ASCII digit `'0' + v`
+            let mask = (0..10u16)
+                .filter(|&v| {
+                    raw_data
+                        .binary_search_by_key(&(0x30 + v as u32), |r| r.code_point)
+                        .is_ok_and(|i| raw_data[i].decimal_digit_value == Some(v as u32))
+                })
+                .fold(0u16, |mask, v| mask | 1 << v);
+            quote! {
+                static DIGIT_ASCII_MASK: u16 = #mask;
+
+                /// Writes the digit value of each character of `chars` into `out`, in order, and
+                /// returns the number of values written, the shorter of the two lengths.
+                pub fn get_digit_values(chars: &[char], out: &mut [::std::option::Option<u8>]) -> usize {
+                    let len = chars.len().min(out.len());
+                    let mut src_chunks = chars[..len].chunks_exact(16);
+                    let mut dst_chunks = out[..len].chunks_exact_mut(16);
+
+                    for (src, dst) in (&mut src_chunks).zip(&mut dst_chunks) {
+                        // Narrows the 16 characters into `u8` lanes, all ASCII if none of them
+                        // has a bit above the 7th
+                        let mut lanes = [0u8; 16];
+                        let mut any = 0u32;
+                        for (lane, &c) in lanes.iter_mut().zip(src) {
+                            *lane = c as u8;
+                            any |= c as u32;
+                        }
+                        if any < 0x80 {
+                            for (dst, &lane) in dst.iter_mut().zip(&lanes) {
+                                let value = lane.wrapping_sub(b'0');
+                                *dst = if value < 10 && (DIGIT_ASCII_MASK >> (value & 15)) & 1 != 0 {
+                                    ::std::option::Option::Some(value)
+                                } else {
+                                    ::std::option::Option::None
+                                };
+                            }
+                        } else {
+                            for (dst, &c) in dst.iter_mut().zip(src) {
+                                *dst = get_digit_value(c);
+                            }
+                        }
+                    }
+
+                    let dst = dst_chunks.into_remainder();
+                    for (dst, &c) in dst.iter_mut().zip(src_chunks.remainder()) {
+                        *dst = get_digit_value(c);
+                    }
+                    len
+                }
+            }
+        } else {
+            quote! {}
+        };
+
         quote! {
             static DIGIT_STARTS: [u32; #len] = [ #(#starts),* ];
             static DIGIT_ENDS:   [u32; #len] = [ #(#ends),*   ];
@@ -592,6 +1325,8 @@ impl<'a> UnipropsBuilder<'a> {
                 }
                 ::std::option::Option::None
             }
+
+            #batch
         }
     }
 }
@@ -0,0 +1,1082 @@
+//! Generation of lookup tables for properties that map codepoint ranges to a single value.
+//!
+//! Every range-valued property (`Category`, `age`, ...) goes through [`PropertyTable`], so all
+//! of them share the same [`LookupStrategy`] implementations.
+
+use std::collections::{BTreeSet, HashMap};
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use crate::{LookupObjective, LookupStrategy, MappingGroup};
+
+const MAX_CODEPOINT: u32 = 0x10FFFF;
+
+/// Number of `u64` words sharing one entry of the bitset chunk index, i.e. 1024 codepoints.
+const BITSET_CHUNK_WORDS: usize = 16;
+const _: () = assert!(BITSET_CHUNK_WORDS.is_power_of_two());
+
+/// Widest packed value supported by [`PropertyTable::generate_blocks`], so that a value always
+/// fits into two consecutive bytes.
+const MAX_PACKED_BITS: u32 = 9;
+
+/// Average number of keys per bucket of the perfect hash.
+const PHF_BUCKET_SIZE: usize = 4;
+
+/// Largest key set of the perfect hash. With one slot per key and `u16` pilots, the last buckets
+/// of bigger sets rarely find free slots, so tables above it fall back to a binary search.
+const PHF_MAX_KEYS: usize = 1 << 16;
+
+/// Number of seeds tried before giving up on the perfect hash.
+const PHF_MAX_SEEDS: u32 = 64;
+
+/// Cache line size assumed by the cost model of [`LookupStrategy::Auto`].
+const CACHE_LINE: usize = 64;
+
+/// Layout options shared by all generated tables, configured on the builder.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TableOptions {
+    pub strategy: LookupStrategy,
+    /// Whether trie blocks are bit-packed, see `UnipropsBuilder::with_packed_blocks`.
+    pub packed_blocks: bool,
+}
+
+/// Placement of the unique trie blocks in the `BLOCKS` static.
+struct BlockLayout<'a> {
+    /// Values of the `BLOCKS` static.
+    values: Vec<Option<&'a str>>,
+    /// Start of every unique block in `values` if the blocks overlap, otherwise block `i`
+    /// starts at `i << shift`.
+    starts: Option<Vec<usize>>,
+    shift: u8,
+}
+
+impl<'a> BlockLayout<'a> {
+    /// Lays out the unique blocks of `2^shift` values in `BLOCKS`.
+    ///
+    /// Each block is overlapped with the longest suffix of the previous ones that it starts with.
+    /// The indices then store the start of each block instead of its number, so overlapping is
+    /// only kept when the starts fit into the same index type as the block numbers.
+    fn new(unique_blocks: &[Vec<Option<&'a str>>], shift: u8) -> Self {
+        let size = 1usize << shift;
+        let mut values: Vec<Option<&str>> = Vec::new();
+        let mut starts = Vec::with_capacity(unique_blocks.len());
+        for block in unique_blocks {
+            let tail = &values[values.len().saturating_sub(size - 1)..];
+            let overlap = overlap_len(tail, block);
+            starts.push(values.len() - overlap);
+            values.extend_from_slice(&block[overlap..]);
+        }
+
+        let max_start = starts.last().copied().unwrap_or(0);
+        let same_width = index_width(max_start + 1)
+            .is_some_and(|width| Some(width) <= index_width(unique_blocks.len()));
+        if same_width && values.len() < unique_blocks.len() << shift {
+            Self {
+                values,
+                starts: Some(starts),
+                shift,
+            }
+        } else {
+            Self {
+                values: unique_blocks.concat(),
+                starts: None,
+                shift,
+            }
+        }
+    }
+
+    /// Converts unique block numbers into the values stored in the indices.
+    fn index_values(&self, block_indices: &[usize]) -> Vec<usize> {
+        match &self.starts {
+            Some(starts) => block_indices.iter().map(|&idx| starts[idx]).collect(),
+            None => block_indices.to_vec(),
+        }
+    }
+}
+
+/// Description of a single generated property table.
+pub(crate) struct PropertyTable<'a> {
+    /// Name used to derive identifiers of the generated items, e.g. `Category` produces
+    /// `CATEGORY_INDICES`, `CATEGORY_BLOCKS` and `CategoryBounds`.
+    pub name: &'a str,
+    /// Type of the looked up value.
+    pub value_type: TokenStream,
+    /// Size in bytes of `value_type`, as stored in binary search entries and packed palettes,
+    /// used to measure the layouts of [`LookupStrategy::Auto`].
+    pub value_size: usize,
+    /// Size in bytes of `Option<value_type>`, the element of unpacked trie blocks.
+    pub option_size: usize,
+    /// Sorted, non-overlapping ranges of codepoints sharing a value.
+    pub groups: &'a [MappingGroup],
+    /// Converts a group value into an expression of type `value_type`.
+    pub value_tokens: &'a dyn Fn(&str) -> TokenStream,
+}
+
+impl PropertyTable<'_> {
+    /// Generates the static tables and a block body evaluating to `Option<value_type>` for the
+    /// character `c`.
+    pub(crate) fn generate(&self, options: TableOptions) -> (TokenStream, TokenStream) {
+        match options.strategy {
+            LookupStrategy::BSearch => self.generate_bsearch(),
+            LookupStrategy::Eytzinger => self.generate_eytzinger(),
+            LookupStrategy::PerfectHash => self.generate_perfect_hash(),
+            LookupStrategy::Trie { shift } => self.generate_trie(shift, options.packed_blocks),
+            LookupStrategy::Trie3 { shift1, shift2 } => {
+                self.generate_trie3(shift1, shift2, options.packed_blocks)
+            }
+            LookupStrategy::Auto { objective } => self.generate(TableOptions {
+                strategy: self.select_strategy(objective, options.packed_blocks),
+                ..options
+            }),
+        }
+    }
+
+    /// Picks the layout generated by [`LookupStrategy::Auto`] for this table.
+    fn select_strategy(&self, objective: LookupObjective, packed: bool) -> LookupStrategy {
+        best_layout(objective, self.measure_layouts(packed))
+    }
+
+    /// Returns the candidate layouts of [`LookupStrategy::Auto`] with their size in bytes and
+    /// their probe cost in cache lines.
+    fn measure_layouts(&self, packed: bool) -> Vec<(LookupStrategy, usize, usize)> {
+        let values = self
+            .groups
+            .iter()
+            .map(|g| g.value.as_str())
+            .collect::<BTreeSet<_>>()
+            .len();
+        // Bits needed to number the values and `None`
+        let bits = (usize::BITS - values.leading_zeros()).max(1) as usize;
+        let (value_size, option_size) = (self.value_size, self.option_size);
+        let blocks_size = |len: usize| {
+            if packed && bits <= MAX_PACKED_BITS as usize {
+                ((len * bits).div_ceil(8) + 1 + values * value_size, 2)
+            } else {
+                (len * option_size, 1)
+            }
+        };
+        let index_size = |len: usize, count: usize| index_width(count).map(|width| len * width);
+
+        let bsearch_size = self.groups.len() * (8 + value_size.next_multiple_of(4));
+        let mut candidates = vec![(
+            LookupStrategy::BSearch,
+            bsearch_size,
+            bsearch_cost(bsearch_size),
+        )];
+
+        for shift in 5..=10u8 {
+            let (unique_blocks, indices) = self.trie_blocks(shift);
+            let layout = BlockLayout::new(&unique_blocks, shift);
+            let (blocks, block_reads) = blocks_size(layout.values.len());
+            if let Some(index) = index_size(indices.len(), unique_blocks.len()) {
+                candidates.push((
+                    LookupStrategy::Trie { shift },
+                    index + blocks,
+                    1 + block_reads,
+                ));
+            }
+
+            if shift > 7 {
+                continue;
+            }
+            for shift1 in shift + 3..=shift + 6 {
+                let chunk_len = 1 << (shift1 - shift);
+                let mut unique_chunks = HashMap::new();
+                for chunk in indices.chunks(chunk_len) {
+                    let next = unique_chunks.len();
+                    unique_chunks.entry(chunk).or_insert(next);
+                }
+                let top = index_size(indices.len() / chunk_len, unique_chunks.len());
+                let chunks = index_size(unique_chunks.len() * chunk_len, unique_blocks.len());
+                if let (Some(top), Some(chunks)) = (top, chunks) {
+                    let strategy = LookupStrategy::Trie3 {
+                        shift1,
+                        shift2: shift,
+                    };
+                    candidates.push((strategy, top + chunks + blocks, 2 + block_reads));
+                }
+            }
+        }
+        candidates
+    }
+
+    /// Generates the static tables and a block body evaluating to `bool`, `true` for the
+    /// codepoints in `groups`. The values of the groups are ignored.
+    ///
+    /// The trie strategies store one bit per codepoint instead of one `Option<value_type>`, see
+    /// [`Self::generate_bitset`].
+    pub(crate) fn generate_membership(&self, options: TableOptions) -> (TokenStream, TokenStream) {
+        match options.strategy {
+            LookupStrategy::BSearch => {
+                let (statics, body) = self.generate_bsearch();
+                (
+                    statics,
+                    quote! { ::std::option::Option::is_some(&{ #body }) },
+                )
+            }
+            LookupStrategy::Eytzinger => {
+                let (statics, body) = self.generate_eytzinger();
+                (
+                    statics,
+                    quote! { ::std::option::Option::is_some(&{ #body }) },
+                )
+            }
+            LookupStrategy::PerfectHash => {
+                let (statics, body) = self.generate_perfect_hash();
+                (
+                    statics,
+                    quote! { ::std::option::Option::is_some(&{ #body }) },
+                )
+            }
+            LookupStrategy::Trie { .. } | LookupStrategy::Trie3 { .. } => self.generate_bitset(),
+            LookupStrategy::Auto { objective } => {
+                let (indices, chunks, words) = self.bitset_tables();
+                let index_width = if chunks.len() / BITSET_CHUNK_WORDS <= 0x100 {
+                    1
+                } else {
+                    2
+                };
+                let chunk_width = if words.len() <= 0x100 { 1 } else { 2 };
+                let bitset_size =
+                    indices.len() * index_width + chunks.len() * chunk_width + words.len() * 8;
+                let bsearch_size = self.groups.len() * 8;
+
+                // Every trie strategy generates the same bitset
+                let strategy = best_layout(
+                    objective,
+                    vec![
+                        (LookupStrategy::Trie { shift: 8 }, bitset_size, 3),
+                        (
+                            LookupStrategy::BSearch,
+                            bsearch_size,
+                            bsearch_cost(bsearch_size),
+                        ),
+                    ],
+                );
+                self.generate_membership(TableOptions {
+                    strategy,
+                    ..options
+                })
+            }
+        }
+    }
+
+    fn static_ident(&self, suffix: &str) -> proc_macro2::Ident {
+        let mut prefix = String::new();
+        for (i, ch) in self.name.chars().enumerate() {
+            if ch.is_uppercase() && i > 0 {
+                prefix.push('_');
+            }
+            prefix.push(ch.to_ascii_uppercase());
+        }
+        format_ident!("{}_{}", prefix, suffix)
+    }
+
+    fn generate_bsearch(&self) -> (TokenStream, TokenStream) {
+        let value_type = &self.value_type;
+        let bounds_ident = format_ident!("{}Bounds", self.name);
+        let lookup_ident = self.static_ident("LOOKUP");
+
+        let lookup = self
+            .groups
+            .iter()
+            .map(|group| {
+                let value = (self.value_tokens)(&group.value);
+                let (start, end) = (group.start, group.end);
+
+                quote! {
+                    #bounds_ident { start: #start, end: #end, value: #value }
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let len = lookup.len();
+
+        let statics = quote! {
+            struct #bounds_ident {
+                start: u32,
+                end: u32,
+                value: #value_type,
+            }
+
+            static #lookup_ident: [#bounds_ident; #len] = [
+                #(#lookup),*
+            ];
+        };
+
+        let body = quote! {
+            #lookup_ident.binary_search_by(| g | {
+                let code_point = c as u32;
+
+                if code_point < g.start {
+                    ::core::cmp::Ordering::Greater
+                } else if code_point > g.end {
+                    ::core::cmp::Ordering::Less
+                } else {
+                    ::core::cmp::Ordering::Equal
+                }
+            })
+            .ok()
+            .map(| i |
+                // SAFETY: We found an element with index i just now, it MUST be in array
+                unsafe { #lookup_ident.get_unchecked(i) }.value
+            )
+        };
+
+        (statics, body)
+    }
+
+    /// Generates a branchless search over the range starts laid out in Eytzinger (BFS) order:
+    /// the children of `STARTS[k]` are `STARTS[2k]` and `STARTS[2k + 1]`, so the first levels
+    /// of the search share a few cache lines. `ENDS` and `VALUES` use the same order.
+    ///
+    /// The tree is padded to a complete one with `u32::MAX` starts, which are never taken, so
+    /// the search loop has a constant trip count.
+    fn generate_eytzinger(&self) -> (TokenStream, TokenStream) {
+        let value_type = &self.value_type;
+        let levels = usize::BITS - self.groups.len().leading_zeros();
+        let len = (1usize << levels) - 1;
+
+        // `order[k]` is the group at position `k` of the 1-based Eytzinger layout, `None` for
+        // padding
+        fn fill(order: &mut [Option<usize>], next: &mut usize, count: usize, k: usize) {
+            if k < order.len() {
+                fill(order, next, count, 2 * k);
+                order[k] = (*next < count).then_some(*next);
+                *next += 1;
+                fill(order, next, count, 2 * k + 1);
+            }
+        }
+        let mut order = vec![None; len + 1];
+        fill(&mut order, &mut 0, self.groups.len(), 1);
+
+        let groups = order[1..].iter().map(|i| i.map(|i| &self.groups[i]));
+        let starts = groups.clone().map(|g| g.map_or(u32::MAX, |g| g.start));
+        let ends = groups.clone().map(|g| g.map_or(0, |g| g.end));
+        // Padding values are never read, any value of the right type will do
+        let values = groups.map(|g| {
+            let g = g.or(self.groups.first()).unwrap();
+            (self.value_tokens)(&g.value)
+        });
+        let starts_len = len + 1;
+
+        let starts_ident = self.static_ident("EYTZINGER_STARTS");
+        let ends_ident = self.static_ident("EYTZINGER_ENDS");
+        let values_ident = self.static_ident("EYTZINGER_VALUES");
+
+        // Index 0 of STARTS is unused
+        let statics = quote! {
+            static #starts_ident: [u32; #starts_len] = [0, #(#starts),*];
+            static #ends_ident: [u32; #len] = [#(#ends),*];
+            static #values_ident: [#value_type; #len] = [#(#values),*];
+        };
+
+        let body = quote! {
+            let cp = c as u32;
+            let mut k = 1usize;
+            for _ in 0..#levels {
+                // SAFETY: k < 2^levels = STARTS.len() before the last iteration
+                k = 2 * k + (unsafe { *#starts_ident.get_unchecked(k) } <= cp) as usize;
+            }
+            // The last right turn of the path is the last range starting at or before `cp`
+            k >>= k.trailing_zeros() + 1;
+            if k == 0 {
+                None
+            } else {
+                // SAFETY: 1 <= k < 2^levels, so k - 1 is within ENDS and VALUES
+                unsafe {
+                    if cp <= *#ends_ident.get_unchecked(k - 1) {
+                        Some(*#values_ident.get_unchecked(k - 1))
+                    } else {
+                        None
+                    }
+                }
+            }
+        };
+
+        (statics, body)
+    }
+
+    /// Generates a minimal perfect hash over the codepoints of `groups`, in the style of CHD and
+    /// PtrHash: every key falls into a bucket, and every bucket stores a `u16` pilot such that
+    /// `hash(key ^ seed ^ pilot)` sends its keys to free slots of a table with exactly one slot
+    /// per key. A lookup hashes twice and compares the key stored in the slot.
+    ///
+    /// Tables with more than [`PHF_MAX_KEYS`] codepoints are generated with
+    /// [`Self::generate_bsearch`] instead.
+    fn generate_perfect_hash(&self) -> (TokenStream, TokenStream) {
+        let key_count = self
+            .groups
+            .iter()
+            .map(|g| (g.end - g.start) as usize + 1)
+            .sum::<usize>();
+        if key_count > PHF_MAX_KEYS {
+            return self.generate_bsearch();
+        }
+
+        let value_type = &self.value_type;
+        let keys = self
+            .groups
+            .iter()
+            .flat_map(|g| (g.start..=g.end).map(move |cp| (cp, g.value.as_str())))
+            .collect::<Vec<_>>();
+        let n = keys.len();
+        let buckets = n.div_ceil(PHF_BUCKET_SIZE).max(1);
+
+        let (seed, pilots, slots) = (0..PHF_MAX_SEEDS)
+            .find_map(|seed| build_perfect_hash(&keys, buckets, seed).map(|(p, s)| (seed, p, s)))
+            .unwrap_or_else(|| {
+                panic!(
+                    "No perfect hash seed found for the {n} codepoints of {} in {PHF_MAX_SEEDS} \
+                     attempts, use another LookupStrategy",
+                    self.name
+                )
+            });
+
+        let slot_keys = slots.iter().map(|&i| keys[i].0);
+        let slot_values = slots.iter().map(|&i| (self.value_tokens)(keys[i].1));
+
+        let pilots_ident = self.static_ident("PHF_PILOTS");
+        let keys_ident = self.static_ident("PHF_KEYS");
+        let values_ident = self.static_ident("PHF_VALUES");
+        let (n_u64, buckets_u64) = (n as u64, buckets as u64);
+
+        let statics = quote! {
+            static #pilots_ident: [u16; #buckets] = [#(#pilots),*];
+            static #keys_ident: [u32; #n] = [#(#slot_keys),*];
+            static #values_ident: [#value_type; #n] = [#(#slot_values),*];
+        };
+
+        if n == 0 {
+            return (statics, quote! { { let _ = c; None } });
+        }
+
+        let body = quote! {
+            #[inline(always)]
+            fn mix(x: u32) -> u32 {
+                let x = (x ^ (x >> 16)).wrapping_mul(0x7FEB_352D);
+                let x = (x ^ (x >> 15)).wrapping_mul(0x846C_A68B);
+                x ^ (x >> 16)
+            }
+
+            let cp = c as u32;
+            let bucket = ((mix(cp ^ #seed) as u64 * #buckets_u64) >> 32) as usize;
+            // SAFETY: `(hash * len) >> 32` is always below len
+            unsafe {
+                let pilot = *#pilots_ident.get_unchecked(bucket) as u32;
+                let slot = ((mix(cp ^ #seed ^ pilot.wrapping_mul(0x9E37_79B9)) as u64 * #n_u64) >> 32) as usize;
+                if *#keys_ident.get_unchecked(slot) == cp {
+                    Some(*#values_ident.get_unchecked(slot))
+                } else {
+                    None
+                }
+            }
+        };
+
+        (statics, body)
+    }
+
+    /// Builds the tables of [`Self::generate_bitset`]: the chunk index of every
+    /// `64 * BITSET_CHUNK_WORDS` codepoints, the word indices of the unique chunks and the unique
+    /// words.
+    fn bitset_tables(&self) -> (Vec<usize>, Vec<usize>, Vec<u64>) {
+        let mut bits = vec![0u64; (MAX_CODEPOINT as usize + 1) / 64];
+        for group in self.groups {
+            for cp in group.start..=group.end {
+                bits[cp as usize / 64] |= 1 << (cp % 64);
+            }
+        }
+
+        // The empty word comes first, so unlisted chunks share index 0
+        let mut words = vec![0u64];
+        let mut word_indices = HashMap::from([(0u64, 0usize)]);
+        let mut chunks: Vec<usize> = Vec::new();
+        let mut chunk_indices: HashMap<Vec<usize>, usize> = HashMap::new();
+        let mut indices = Vec::new();
+        for chunk in bits.chunks(BITSET_CHUNK_WORDS) {
+            let chunk = chunk
+                .iter()
+                .map(|&word| {
+                    *word_indices.entry(word).or_insert_with(|| {
+                        words.push(word);
+                        words.len() - 1
+                    })
+                })
+                .collect::<Vec<_>>();
+            let next = chunk_indices.len();
+            let idx = *chunk_indices.entry(chunk.clone()).or_insert_with(|| {
+                chunks.extend(&chunk);
+                next
+            });
+            indices.push(idx);
+        }
+        (indices, chunks, words)
+    }
+
+    /// Generates a bitset trie similar to `bitset_search` of the Rust standard library: the
+    /// codepoint space is split into `u64` words, which are deduplicated, and every
+    /// `BITSET_CHUNK_WORDS` words are grouped into deduplicated chunks of word indices.
+    fn generate_bitset(&self) -> (TokenStream, TokenStream) {
+        let max_codepoint = MAX_CODEPOINT;
+        let word_shift = BITSET_CHUNK_WORDS.trailing_zeros();
+        let chunk_shift = 6 + word_shift;
+        let word_mask = BITSET_CHUNK_WORDS as u32 - 1;
+        let (indices, chunks, words) = self.bitset_tables();
+        let chunk_count = chunks.len() / BITSET_CHUNK_WORDS;
+
+        let (index_type, indices_tokens) = index_tokens(&indices, chunk_count);
+        let (chunk_type, chunks_tokens) = index_tokens(&chunks, words.len());
+
+        let indices_len = indices.len();
+        let chunks_len = chunks.len();
+        let words_len = words.len();
+
+        let indices_ident = self.static_ident("BITSET_INDICES");
+        let chunks_ident = self.static_ident("BITSET_CHUNKS");
+        let words_ident = self.static_ident("BITSET_WORDS");
+
+        let statics = quote! {
+            static #indices_ident: [#index_type; #indices_len] = [
+                #(#indices_tokens),*
+            ];
+
+            static #chunks_ident: [#chunk_type; #chunks_len] = [
+                #(#chunks_tokens),*
+            ];
+
+            static #words_ident: [u64; #words_len] = [
+                #(#words),*
+            ];
+        };
+
+        let body = quote! {
+            let cp = c as u32;
+            if cp > #max_codepoint {
+                false
+            } else {
+                // SAFETY: Arrays are generated to cover up to 0x10FFFF
+                unsafe {
+                    let chunk_idx = *#indices_ident.get_unchecked((cp >> #chunk_shift) as usize) as usize;
+                    let word_pos = (chunk_idx << #word_shift) + ((cp >> 6) & #word_mask) as usize;
+                    let word_idx = *#chunks_ident.get_unchecked(word_pos) as usize;
+                    (*#words_ident.get_unchecked(word_idx) >> (cp & 63)) & 1 != 0
+                }
+            }
+        };
+
+        (statics, body)
+    }
+
+    /// Splits the codepoint space into blocks of `2^shift` values and deduplicates them.
+    ///
+    /// Returns the unique blocks and, for every block of the codepoint space, the index of its
+    /// unique block.
+    fn trie_blocks(&self, shift: u8) -> (Vec<Vec<Option<&str>>>, Vec<usize>) {
+        // Values are interned as `i + 1` for the `i`-th distinct value and `0` for `None`, so
+        // that blocks are hashed and compared as plain integer slices
+        let mut values: Vec<&str> = Vec::new();
+        let mut value_codes: HashMap<&str, u32> = HashMap::new();
+        let mut codes = vec![0u32; MAX_CODEPOINT as usize + 1];
+        for g in self.groups {
+            let code = *value_codes.entry(g.value.as_str()).or_insert_with(|| {
+                values.push(g.value.as_str());
+                values.len() as u32
+            });
+            codes[g.start as usize..=g.end as usize].fill(code);
+        }
+
+        let mut unique_blocks: Vec<Vec<Option<&str>>> = Vec::new();
+        let mut block_indices: HashMap<&[u32], usize> = HashMap::new();
+        let mut indices: Vec<usize> = Vec::with_capacity(codes.len() >> shift);
+        for block in codes.chunks(1 << shift) {
+            let idx = *block_indices.entry(block).or_insert_with(|| {
+                let block = block.iter().map(|&code| match code {
+                    0 => None,
+                    code => Some(values[code as usize - 1]),
+                });
+                unique_blocks.push(block.collect());
+                unique_blocks.len() - 1
+            });
+            indices.push(idx);
+        }
+
+        (unique_blocks, indices)
+    }
+
+    /// Generates the `BLOCKS` static and an expression reading the value at `offset` of the
+    /// block `block_idx`, as stored in the indices (see [`BlockLayout::index_values`]).
+    ///
+    /// Packed blocks store a code per value, `0` for `None` and `i + 1` for the `i`-th distinct
+    /// value of a `PALETTE`, using the minimal number of bits. A code is read from the two
+    /// bytes containing it with a shift and a mask.
+    fn generate_blocks(&self, layout: &BlockLayout, packed: bool) -> (TokenStream, TokenStream) {
+        let value_type = &self.value_type;
+        let blocks_ident = self.static_ident("BLOCKS");
+        let values = layout
+            .values
+            .iter()
+            .flatten()
+            .copied()
+            .collect::<BTreeSet<_>>();
+        let bits = (usize::BITS - values.len().leading_zeros()).max(1);
+
+        let final_pos = if layout.starts.is_some() {
+            quote! { let final_pos = block_idx + offset; }
+        } else {
+            let shift = layout.shift;
+            quote! { let final_pos = (block_idx << #shift) + offset; }
+        };
+
+        if !packed || bits > MAX_PACKED_BITS {
+            let blocks_tokens = layout.values.iter().map(|value| match value {
+                Some(value) => {
+                    let value = (self.value_tokens)(value);
+                    quote! { Some(#value) }
+                }
+                None => quote! { None },
+            });
+            let blocks_len = layout.values.len();
+
+            let statics = quote! {
+                static #blocks_ident: [Option<#value_type>; #blocks_len] = [
+                    #(#blocks_tokens),*
+                ];
+            };
+            let read = quote! {{
+                #final_pos
+                *#blocks_ident.get_unchecked(final_pos)
+            }};
+            return (statics, read);
+        }
+
+        let codes = values
+            .iter()
+            .enumerate()
+            .map(|(i, &value)| (value, i as u32 + 1))
+            .collect::<HashMap<_, _>>();
+        let count = layout.values.len();
+        // One extra byte, so the two-byte read of the last code stays in bounds
+        let mut bytes = vec![0u8; (count * bits as usize).div_ceil(8) + 1];
+        for (pos, value) in layout.values.iter().enumerate() {
+            let code = value.map_or(0, |value| codes[value]);
+            let bit = pos * bits as usize;
+            let shifted = code << (bit % 8);
+            bytes[bit / 8] |= shifted as u8;
+            bytes[bit / 8 + 1] |= (shifted >> 8) as u8;
+        }
+
+        let values_ident = self.static_ident("PALETTE");
+        let values_len = values.len();
+        let values_tokens = values.iter().map(|value| (self.value_tokens)(value));
+        let bytes_len = bytes.len();
+        let mask = (1u16 << bits) - 1;
+        let doc = format!(
+            " {count} values packed at {bits} bits into {bytes_len} bytes, instead of {count} \
+             `Option<{value_type}>`s."
+        );
+
+        let statics = quote! {
+            static #values_ident: [#value_type; #values_len] = [
+                #(#values_tokens),*
+            ];
+
+            #[doc = #doc]
+            static #blocks_ident: [u8; #bytes_len] = [
+                #(#bytes),*
+            ];
+        };
+        let read = quote! {{
+            #final_pos
+            let bit = final_pos * #bits as usize;
+            let pair = u16::from_le_bytes([
+                *#blocks_ident.get_unchecked(bit >> 3),
+                *#blocks_ident.get_unchecked((bit >> 3) + 1),
+            ]);
+            match ((pair >> (bit & 7)) & #mask) as usize {
+                0 => None,
+                code => Some(*#values_ident.get_unchecked(code - 1)),
+            }
+        }};
+        (statics, read)
+    }
+
+    fn generate_trie(&self, shift: u8, packed: bool) -> (TokenStream, TokenStream) {
+        let size: u32 = 1 << (shift as u32);
+        let mask: u32 = size - 1;
+        let max_codepoint = MAX_CODEPOINT;
+
+        let (unique_blocks, indices) = self.trie_blocks(shift);
+        let layout = BlockLayout::new(&unique_blocks, shift);
+        let indices = layout.index_values(&indices);
+        let (index_type, indices_tokens) = index_tokens(&indices, unique_blocks.len());
+
+        let indices_len = indices.len();
+        let (blocks_statics, read_block) = self.generate_blocks(&layout, packed);
+
+        let indices_ident = self.static_ident("INDICES");
+
+        let statics = quote! {
+            static #indices_ident:[#index_type; #indices_len] = [
+                #(#indices_tokens),*
+            ];
+
+            #blocks_statics
+        };
+
+        let body = quote! {
+            let cp = c as u32;
+            if cp > #max_codepoint {
+                None
+            } else {
+                let index_idx = (cp >> #shift) as usize;
+
+                // SAFETY: Arrays are generated to cover up to 0x10FFFF
+                unsafe {
+                    let block_idx = *#indices_ident.get_unchecked(index_idx) as usize;
+                    let offset = (cp & #mask) as usize;
+                    #read_block
+                }
+            }
+        };
+
+        (statics, body)
+    }
+
+    /// Generates the `INDICES`, `INDEX_CHUNKS` and `BLOCKS` statics of a three-level trie and
+    /// an expression reading the value at `offset` of the block `block_idx` read from
+    /// `INDEX_CHUNKS`.
+    fn trie3_statics(&self, shift1: u8, shift2: u8, packed: bool) -> (TokenStream, TokenStream) {
+        let chunk_shift = shift1 - shift2;
+        let (unique_blocks, block_indices) = self.trie_blocks(shift2);
+
+        // The block indices are deduplicated again in chunks of `2^(shift1 - shift2)`
+        let mut unique_chunks: Vec<&[usize]> = Vec::new();
+        let mut chunk_indices: HashMap<&[usize], usize> = HashMap::new();
+        let mut indices: Vec<usize> = Vec::new();
+        for chunk in block_indices.chunks(1 << chunk_shift) {
+            let idx = *chunk_indices.entry(chunk).or_insert_with(|| {
+                unique_chunks.push(chunk);
+                unique_chunks.len() - 1
+            });
+            indices.push(idx);
+        }
+        let layout = BlockLayout::new(&unique_blocks, shift2);
+        let chunks = layout.index_values(&unique_chunks.concat());
+
+        let (index_type, indices_tokens) = index_tokens(&indices, unique_chunks.len());
+        let (chunk_type, chunks_tokens) = index_tokens(&chunks, unique_blocks.len());
+        let (blocks_statics, read_block) = self.generate_blocks(&layout, packed);
+
+        let indices_len = indices.len();
+        let chunks_len = chunks.len();
+
+        let indices_ident = self.static_ident("INDICES");
+        let chunks_ident = self.static_ident("INDEX_CHUNKS");
+
+        let statics = quote! {
+            static #indices_ident: [#index_type; #indices_len] = [
+                #(#indices_tokens),*
+            ];
+
+            static #chunks_ident: [#chunk_type; #chunks_len] = [
+                #(#chunks_tokens),*
+            ];
+
+            #blocks_statics
+        };
+
+        (statics, read_block)
+    }
+
+    fn generate_trie3(&self, shift1: u8, shift2: u8, packed: bool) -> (TokenStream, TokenStream) {
+        assert!(
+            shift2 < shift1 && shift1 <= 20,
+            "Trie3 requires shift2 < shift1 <= 20, got shift1 = {shift1}, shift2 = {shift2}"
+        );
+        let max_codepoint = MAX_CODEPOINT;
+        let mask: u32 = (1 << shift2) - 1;
+        let chunk_shift = shift1 - shift2;
+        let chunk_mask: u32 = (1 << chunk_shift) - 1;
+
+        let (statics, read_block) = self.trie3_statics(shift1, shift2, packed);
+        let indices_ident = self.static_ident("INDICES");
+        let chunks_ident = self.static_ident("INDEX_CHUNKS");
+
+        let body = quote! {
+            let cp = c as u32;
+            if cp > #max_codepoint {
+                None
+            } else {
+                // SAFETY: Arrays are generated to cover up to 0x10FFFF
+                unsafe {
+                    let chunk_idx = *#indices_ident.get_unchecked((cp >> #shift1) as usize) as usize;
+                    let chunk_pos = (chunk_idx << #chunk_shift) + ((cp >> #shift2) & #chunk_mask) as usize;
+                    let block_idx = *#chunks_ident.get_unchecked(chunk_pos) as usize;
+                    let offset = (cp & #mask) as usize;
+                    #read_block
+                }
+            }
+        };
+
+        (statics, body)
+    }
+
+    /// Generates a lookup keyed directly by the bytes of a UTF-8 sequence, like the UTF-8 fast
+    /// path of ICU's tries: a three-level trie with 64-entry levels, so that the lead byte (plus
+    /// the first continuation byte of 4-byte sequences) selects a chunk, and each following
+    /// continuation byte selects a block and a value without reassembling the codepoint.
+    ///
+    /// The body validates the sequence at the start of `bytes` and evaluates to
+    /// `Option<(value_type, usize)>`. It uses `?`, so the enclosing function must return an
+    /// `Option`.
+    pub(crate) fn generate_utf8(&self, packed: bool) -> (TokenStream, TokenStream) {
+        let (statics, read_block) = self.trie3_statics(12, 6, packed);
+        let indices_ident = self.static_ident("INDICES");
+        let chunks_ident = self.static_ident("INDEX_CHUNKS");
+
+        let body = quote! {
+            // The low 6 bits of the continuation byte `i`, if it is in `range`
+            let trail = |i: usize, range: ::core::ops::RangeInclusive<u8>| {
+                bytes
+                    .get(i)
+                    .filter(|b| range.contains(b))
+                    .map(|b| (b & 0x3F) as usize)
+            };
+
+            // `top` is `cp >> 12`, `middle` the next 6 bits and `low` the last 6 bits
+            let (top, middle, low, len) = match *bytes.first()? {
+                b @ 0x00..=0x7F => (0, (b >> 6) as usize, (b & 0x3F) as usize, 1),
+                b @ 0xC2..=0xDF => (0, (b & 0x1F) as usize, trail(1, 0x80..=0xBF)?, 2),
+                b @ 0xE0..=0xEF => {
+                    // Excludes overlong forms and surrogates
+                    let range = match b {
+                        0xE0 => 0xA0..=0xBF,
+                        0xED => 0x80..=0x9F,
+                        _ => 0x80..=0xBF,
+                    };
+                    ((b & 0x0F) as usize, trail(1, range)?, trail(2, 0x80..=0xBF)?, 3)
+                }
+                b @ 0xF0..=0xF4 => {
+                    // Excludes overlong forms and codepoints above U+10FFFF
+                    let range = match b {
+                        0xF0 => 0x90..=0xBF,
+                        0xF4 => 0x80..=0x8F,
+                        _ => 0x80..=0xBF,
+                    };
+                    let top = (((b & 0x07) as usize) << 6) | trail(1, range)?;
+                    (top, trail(2, 0x80..=0xBF)?, trail(3, 0x80..=0xBF)?, 4)
+                }
+                _ => return None,
+            };
+
+            // SAFETY: `top <= 0x10FFFF >> 12` and the other indices are below 64, so every read
+            // stays within arrays generated to cover up to 0x10FFFF
+            let value = unsafe {
+                let chunk_idx = *#indices_ident.get_unchecked(top) as usize;
+                let block_idx = *#chunks_ident.get_unchecked((chunk_idx << 6) + middle) as usize;
+                let offset = low;
+                #read_block
+            };
+            value.map(|value| (value, len))
+        };
+
+        (statics, body)
+    }
+}
+
+/// The hash of the generated perfect hash lookups (lowbias32 by Chris Wellons).
+fn phf_mix(x: u32) -> u32 {
+    let x = (x ^ (x >> 16)).wrapping_mul(0x7FEB_352D);
+    let x = (x ^ (x >> 15)).wrapping_mul(0x846C_A68B);
+    x ^ (x >> 16)
+}
+
+/// Maps a hash to `0..len` with a multiplication instead of a division.
+fn phf_reduce(hash: u32, len: usize) -> usize {
+    ((hash as u64 * len as u64) >> 32) as usize
+}
+
+/// Searches a pilot for every bucket, largest buckets first. Returns the pilots and, for every
+/// slot, the index of its key in `keys`, or `None` if some bucket has no `u16` pilot with
+/// this `seed`.
+fn build_perfect_hash(
+    keys: &[(u32, &str)],
+    buckets: usize,
+    seed: u32,
+) -> Option<(Vec<u16>, Vec<usize>)> {
+    let n = keys.len();
+    let mut bucket_keys = vec![Vec::new(); buckets];
+    for (i, &(cp, _)) in keys.iter().enumerate() {
+        bucket_keys[phf_reduce(phf_mix(cp ^ seed), buckets)].push(i);
+    }
+    let mut order = (0..buckets).collect::<Vec<_>>();
+    order.sort_by_key(|&b| std::cmp::Reverse(bucket_keys[b].len()));
+
+    let mut pilots = vec![0u16; buckets];
+    let mut slots = vec![usize::MAX; n];
+    let mut taken = Vec::new();
+    for bucket in order {
+        let members = &bucket_keys[bucket];
+        if members.is_empty() {
+            break;
+        }
+        let pilot = (0..=u16::MAX).find(|&pilot| {
+            taken.clear();
+            let pilot_hash = (pilot as u32).wrapping_mul(0x9E37_79B9);
+            members.iter().all(|&i| {
+                let slot = phf_reduce(phf_mix(keys[i].0 ^ seed ^ pilot_hash), n);
+                let free = slots[slot] == usize::MAX && !taken.contains(&slot);
+                taken.push(slot);
+                free
+            })
+        })?;
+        pilots[bucket] = pilot;
+        for (&i, &slot) in members.iter().zip(&taken) {
+            slots[slot] = i;
+        }
+    }
+    Some((pilots, slots))
+}
+
+/// Picks the best of `(strategy, size, cost)` candidates according to `objective`.
+fn best_layout(
+    objective: LookupObjective,
+    candidates: Vec<(LookupStrategy, usize, usize)>,
+) -> LookupStrategy {
+    let best = match objective {
+        LookupObjective::Size => candidates
+            .into_iter()
+            .min_by_key(|&(_, size, cost)| (size, cost)),
+        LookupObjective::Speed => candidates
+            .into_iter()
+            .min_by_key(|&(_, size, cost)| (cost, size)),
+    };
+    best.expect("No candidate layout").0
+}
+
+/// Size in bytes of a `&[T]` value, and of `Option<&[T]>`, on the target: two pointer-sized
+/// words. Build scripts run on the host, so the width comes from cargo rather than `usize`.
+pub(crate) fn slice_size() -> usize {
+    let pointer_width = std::env::var("CARGO_CFG_TARGET_POINTER_WIDTH")
+        .ok()
+        .and_then(|bits| bits.parse::<usize>().ok())
+        .map_or(size_of::<usize>(), |bits| bits / 8);
+    2 * pointer_width
+}
+
+/// Probe cost of a binary search over `size` bytes: `log2(lines) + 1` cache lines.
+fn bsearch_cost(size: usize) -> usize {
+    let lines = size.div_ceil(CACHE_LINE).max(1);
+    (usize::BITS - (lines - 1).leading_zeros()) as usize + 1
+}
+
+/// Length of the longest suffix of `tail` that is a prefix of `block`, found in linear time
+/// with the prefix function of `block` (Knuth-Morris-Pratt).
+fn overlap_len<T: PartialEq>(tail: &[T], block: &[T]) -> usize {
+    let mut prefix = vec![0; block.len()];
+    let mut len = 0;
+    for i in 1..block.len() {
+        while len > 0 && block[i] != block[len] {
+            len = prefix[len - 1];
+        }
+        if block[i] == block[len] {
+            len += 1;
+        }
+        prefix[i] = len;
+    }
+
+    let mut len = 0;
+    for value in tail {
+        if len == block.len() {
+            len = prefix[len - 1];
+        }
+        while len > 0 && *value != block[len] {
+            len = prefix[len - 1];
+        }
+        if *value == block[len] {
+            len += 1;
+        }
+    }
+    len
+}
+
+/// Size in bytes of the integer type picked by [`index_tokens`] for `count` elements.
+fn index_width(count: usize) -> Option<usize> {
+    match count {
+        0..=0x100 => Some(1),
+        0x101..=0x10000 => Some(2),
+        _ => None,
+    }
+}
+
+/// Picks the smallest integer type able to index `count` elements and converts `indices` into
+/// literals of that type.
+fn index_tokens(indices: &[usize], count: usize) -> (TokenStream, Vec<TokenStream>) {
+    if count <= (u8::MAX as usize) + 1 {
+        let tokens = indices.iter().map(|&idx| {
+            let val = idx as u8;
+            quote! { #val }
+        });
+        (quote! { u8 }, tokens.collect())
+    } else if count <= (u16::MAX as usize) + 1 {
+        let tokens = indices.iter().map(|&idx| {
+            let val = idx as u16;
+            quote! { #val }
+        });
+        (quote! { u16 }, tokens.collect())
+    } else {
+        (
+            quote! { compile_error!("Shift is too small, u16 overflow") },
+            Vec::new(),
+        )
+    }
+}
+
+/// Flattens a map of codepoint sequences into `SOURCES`, `OFFSETS` and `CHARS` arrays, which
+/// are searched with a binary search over `SOURCES`.
+pub(crate) fn generate_sequence_table(
+    name: &str,
+    sequences: &[(u32, Vec<u32>)],
+) -> (TokenStream, TokenStream) {
+    let sources_ident = format_ident!("{}_SOURCES", name);
+    let offsets_ident = format_ident!("{}_OFFSETS", name);
+    let chars_ident = format_ident!("{}_CHARS", name);
+
+    let sources = sequences.iter().map(|(cp, _)| *cp).collect::<Vec<_>>();
+    let mut offsets = vec![0u16];
+    let mut chars = Vec::new();
+    for (_, sequence) in sequences {
+        chars.extend(
+            sequence
+                .iter()
+                .map(|&cp| char::from_u32(cp).expect("Sequence contains a surrogate")),
+        );
+        offsets.push(u16::try_from(chars.len()).expect("Sequence table exceeds u16 offsets"));
+    }
+
+    let sources_len = sources.len();
+    let offsets_len = offsets.len();
+    let chars_len = chars.len();
+
+    let statics = quote! {
+        static #sources_ident: [u32; #sources_len] = [ #(#sources),* ];
+        static #offsets_ident: [u16; #offsets_len] = [ #(#offsets),* ];
+        static #chars_ident: [char; #chars_len] = [ #(#chars),* ];
+    };
+
+    let body = quote! {
+        let cp = c as u32;
+        #sources_ident.binary_search(&cp).ok().map(|i| {
+            // SAFETY: OFFSETS has one more element than SOURCES and its values are valid
+            // positions in CHARS
+            unsafe {
+                let start = *#offsets_ident.get_unchecked(i) as usize;
+                let end = *#offsets_ident.get_unchecked(i + 1) as usize;
+                #chars_ident.get_unchecked(start..end)
+            }
+        })
+    };
+
+    (statics, body)
+}
@@ -0,0 +1,298 @@
+//! Canonical decomposition (NFD) generated from the decomposition and combining class fields of
+//! `UnicodeData.txt`, and the NFKC_Casefold mapping from `DerivedNormalizationProps.txt`.
+//!
+//! Unlike the other tables, normalization data ignores `.filter()`: a partially decomposing
+//! `nfd` would silently produce wrong results, so it is always built from every record that
+//! passes `assigned_since`.
+
+use std::collections::HashMap;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{
+    Ages, MappingGroup, UnicodeRecord, UnipropsBuilder,
+    binary::DERIVED_NORMALIZATION_PROPS,
+    lookup::{PropertyTable, generate_sequence_table},
+    ucd,
+};
+
+/// Parses the canonical decomposition mapping of a record, ignoring compatibility mappings.
+fn canonical_mapping(record: &UnicodeRecord) -> Option<Vec<u32>> {
+    let decomposition = record.decomposition.as_deref()?;
+    if decomposition.starts_with('<') {
+        return None;
+    }
+    Some(
+        decomposition
+            .split_whitespace()
+            .map(ucd::parse_code_point)
+            .collect(),
+    )
+}
+
+fn decompose_fully(cp: u32, mappings: &HashMap<u32, Vec<u32>>, out: &mut Vec<u32>) {
+    match mappings.get(&cp) {
+        Some(mapping) => {
+            for &part in mapping {
+                decompose_fully(part, mappings, out);
+            }
+        }
+        None => out.push(cp),
+    }
+}
+
+impl UnipropsBuilder<'_> {
+    pub(crate) fn generate_normalization(&self, records: &[UnicodeRecord]) -> TokenStream {
+        let mappings = records
+            .iter()
+            .filter_map(|r| canonical_mapping(r).map(|m| (r.code_point, m)))
+            .collect::<HashMap<_, _>>();
+
+        let mut decompositions = mappings
+            .keys()
+            .map(|&cp| {
+                let mut full = Vec::new();
+                decompose_fully(cp, &mappings, &mut full);
+                (cp, full)
+            })
+            .collect::<Vec<_>>();
+        decompositions.sort();
+
+        let (decomposition_statics, decomposition_body) =
+            generate_sequence_table("DECOMPOSITION", &decompositions);
+
+        let mut combining_classes: Vec<MappingGroup> = Vec::new();
+        for record in records.iter().filter(|r| r.canonical_combining_class != 0) {
+            let value = record.canonical_combining_class.to_string();
+            match combining_classes.last_mut() {
+                Some(last) if last.end + 1 == record.code_point && last.value == value => {
+                    last.end = record.code_point;
+                }
+                _ => combining_classes.push(MappingGroup {
+                    value,
+                    start: record.code_point,
+                    end: record.code_point,
+                }),
+            }
+        }
+
+        let table = PropertyTable {
+            name: "CanonicalCombiningClass",
+            value_type: quote! { u8 },
+            value_size: 1,
+            option_size: 2,
+            groups: &combining_classes,
+            value_tokens: &|value| {
+                let value: u8 = value.parse().unwrap();
+                quote! { #value }
+            },
+        };
+        let (ccc_statics, ccc_body) = table.generate(self.table_options());
+
+        quote! {
+            #decomposition_statics
+            #ccc_statics
+
+            /// Returns the `Canonical_Combining_Class` of `c`, `0` for starters.
+            #[inline(always)]
+            pub fn canonical_combining_class(c: char) -> u8 {
+                let ccc: ::std::option::Option<u8> = { #ccc_body };
+                ccc.unwrap_or(0)
+            }
+
+            /// Returns the full canonical decomposition of `c`, or `None` if `c` decomposes to
+            /// itself. Hangul syllables are decomposed algorithmically by `nfd` and are not
+            /// included.
+            #[inline(always)]
+            pub fn canonical_decomposition(c: char) -> ::std::option::Option<&'static [char]> {
+                #decomposition_body
+            }
+
+            const HANGUL_S_BASE: u32 = 0xAC00;
+            const HANGUL_L_BASE: u32 = 0x1100;
+            const HANGUL_V_BASE: u32 = 0x1161;
+            const HANGUL_T_BASE: u32 = 0x11A7;
+            const HANGUL_T_COUNT: u32 = 28;
+            const HANGUL_N_COUNT: u32 = 21 * HANGUL_T_COUNT;
+            const HANGUL_S_COUNT: u32 = 19 * HANGUL_N_COUNT;
+
+            fn push_canonical_decomposition(c: char, out: &mut ::std::vec::Vec<char>) {
+                let s_index = (c as u32).wrapping_sub(HANGUL_S_BASE);
+                if s_index < HANGUL_S_COUNT {
+                    let l = HANGUL_L_BASE + s_index / HANGUL_N_COUNT;
+                    let v = HANGUL_V_BASE + (s_index % HANGUL_N_COUNT) / HANGUL_T_COUNT;
+                    let t = HANGUL_T_BASE + s_index % HANGUL_T_COUNT;
+                    // SAFETY: Hangul jamo are valid scalar values
+                    unsafe {
+                        out.push(char::from_u32_unchecked(l));
+                        out.push(char::from_u32_unchecked(v));
+                        if t != HANGUL_T_BASE {
+                            out.push(char::from_u32_unchecked(t));
+                        }
+                    }
+                    return;
+                }
+
+                match canonical_decomposition(c) {
+                    ::std::option::Option::Some(chars) => out.extend_from_slice(chars),
+                    ::std::option::Option::None => out.push(c),
+                }
+            }
+
+            /// Applies the Canonical Ordering Algorithm: stably sorts every run of non-starters
+            /// by their combining class.
+            fn canonical_reorder(chars: &mut [char]) {
+                let mut i = 0;
+                while i < chars.len() {
+                    if canonical_combining_class(chars[i]) == 0 {
+                        i += 1;
+                        continue;
+                    }
+                    let start = i;
+                    while i < chars.len() && canonical_combining_class(chars[i]) != 0 {
+                        i += 1;
+                    }
+                    chars[start..i].sort_by_key(|&c| canonical_combining_class(c));
+                }
+            }
+
+            /// Returns the Normalization Form D (canonical decomposition) of `s`.
+            pub fn nfd(s: &str) -> ::std::string::String {
+                let mut chars = ::std::vec::Vec::with_capacity(s.len());
+                for c in s.chars() {
+                    push_canonical_decomposition(c, &mut chars);
+                }
+                canonical_reorder(&mut chars);
+                chars.into_iter().collect()
+            }
+        }
+    }
+
+    /// Generates `nfkc_casefold`. Requires the output of [`Self::generate_normalization`] in
+    /// the same module.
+    pub(crate) fn generate_nfkc_casefold(
+        &self,
+        records: &[UnicodeRecord],
+        ages: Option<&Ages>,
+    ) -> TokenStream {
+        let mut casefold = Vec::new();
+        for entry in ucd::parse_property_file(DERIVED_NORMALIZATION_PROPS)
+            .iter()
+            .filter(|e| e.fields[0] == "NFKC_CF")
+        {
+            let mapping = entry.fields[1]
+                .split_whitespace()
+                .map(ucd::parse_code_point)
+                .collect::<Vec<_>>();
+            for cp in (entry.start..=entry.end).filter(|&cp| self.is_assigned(ages, cp)) {
+                casefold.push((cp, mapping.clone()));
+            }
+        }
+        casefold.sort();
+        let (casefold_statics, casefold_body) = generate_sequence_table("NFKC_CASEFOLD", &casefold);
+
+        // Primary composites: canonical pairs which are not excluded from composition
+        let exclusions =
+            ucd::binary_property_groups(DERIVED_NORMALIZATION_PROPS, "Full_Composition_Exclusion");
+        let is_excluded = |cp: u32| exclusions.iter().any(|g| (g.start..=g.end).contains(&cp));
+        let mut compositions = records
+            .iter()
+            .filter(|r| !is_excluded(r.code_point))
+            .filter_map(|r| match canonical_mapping(r)?.as_slice() {
+                &[first, second] => Some((first, second, r.code_point)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        compositions.sort();
+        let compositions_len = compositions.len();
+        let compositions = compositions.iter().map(|&(first, second, composite)| {
+            let (first, second, composite) = (
+                char::from_u32(first).unwrap(),
+                char::from_u32(second).unwrap(),
+                char::from_u32(composite).unwrap(),
+            );
+            quote! { (#first, #second, #composite) }
+        });
+
+        quote! {
+            #casefold_statics
+
+            static COMPOSITIONS: [(char, char, char); #compositions_len] = [
+                #(#compositions),*
+            ];
+
+            #[inline(always)]
+            fn nfkc_casefold_mapping(c: char) -> ::std::option::Option<&'static [char]> {
+                #casefold_body
+            }
+
+            /// Returns the primary composite of `first` and `second`, if any.
+            fn compose_pair(first: char, second: char) -> ::std::option::Option<char> {
+                let l_index = (first as u32).wrapping_sub(HANGUL_L_BASE);
+                let v_index = (second as u32).wrapping_sub(HANGUL_V_BASE);
+                if l_index < 19 && v_index < 21 {
+                    let s = HANGUL_S_BASE + (l_index * 21 + v_index) * HANGUL_T_COUNT;
+                    return char::from_u32(s);
+                }
+                let s_index = (first as u32).wrapping_sub(HANGUL_S_BASE);
+                let t_index = (second as u32).wrapping_sub(HANGUL_T_BASE);
+                if s_index < HANGUL_S_COUNT && s_index % HANGUL_T_COUNT == 0 && t_index.wrapping_sub(1) < HANGUL_T_COUNT - 1 {
+                    return char::from_u32(first as u32 + t_index);
+                }
+
+                COMPOSITIONS
+                    .binary_search_by(|&(f, s, _)| (f, s).cmp(&(first, second)))
+                    .ok()
+                    .map(|i| COMPOSITIONS[i].2)
+            }
+
+            /// Applies the Canonical Composition Algorithm to a canonically ordered sequence.
+            fn canonical_compose(chars: &[char]) -> ::std::string::String {
+                let mut out = ::std::vec::Vec::<char>::with_capacity(chars.len());
+                let mut starter: ::std::option::Option<usize> = ::std::option::Option::None;
+                // Combining class of the last character appended after the starter
+                let mut last_ccc = 0;
+                for &c in chars {
+                    let ccc = canonical_combining_class(c);
+                    if let ::std::option::Option::Some(i) = starter {
+                        let blocked = i + 1 != out.len() && (last_ccc == 0 || last_ccc >= ccc);
+                        if !blocked {
+                            if let ::std::option::Option::Some(composite) = compose_pair(out[i], c) {
+                                out[i] = composite;
+                                continue;
+                            }
+                        }
+                    }
+                    if ccc == 0 {
+                        starter = ::std::option::Option::Some(out.len());
+                    }
+                    last_ccc = ccc;
+                    out.push(c);
+                }
+                out.into_iter().collect()
+            }
+
+            /// Returns `toNFKC_Casefold(s)`: every character replaced by its `NFKC_Casefold`
+            /// mapping, then normalized to NFC.
+            ///
+            /// Two identifiers match case-insensitively under UAX #31 if their foldings are
+            /// equal, e.g. `"Straße"`, `"STRASSE"` and `"ｓｔｒａｓｓｅ"` all fold to `"strasse"`.
+            pub fn nfkc_casefold(s: &str) -> ::std::string::String {
+                let mut chars = ::std::vec::Vec::with_capacity(s.len());
+                for c in s.chars() {
+                    match nfkc_casefold_mapping(c) {
+                        ::std::option::Option::Some(mapping) => {
+                            for &m in mapping {
+                                push_canonical_decomposition(m, &mut chars);
+                            }
+                        }
+                        ::std::option::Option::None => push_canonical_decomposition(c, &mut chars),
+                    }
+                }
+                canonical_reorder(&mut chars);
+                canonical_compose(&chars)
+            }
+        }
+    }
+}
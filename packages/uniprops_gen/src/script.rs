@@ -0,0 +1,112 @@
+//! Support for the `Script` and `Script_Extensions` properties from `Scripts.txt` and
+//! `ScriptExtensions.txt`.
+
+use std::collections::{BTreeSet, HashMap};
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{
+    Ages, UnipropsBuilder,
+    enumerated::{EnumeratedProperty, variant_ident},
+    lookup::{PropertyTable, slice_size},
+    ucd,
+};
+
+pub(crate) const SCRIPTS: &str = include_str!("../assets/Scripts.txt");
+pub(crate) const SCRIPT_EXTENSIONS: &str = include_str!("../assets/ScriptExtensions.txt");
+pub(crate) const PROPERTY_VALUE_ALIASES: &str = include_str!("../assets/PropertyValueAliases.txt");
+
+const SCRIPT: EnumeratedProperty = EnumeratedProperty {
+    name: "Script",
+    source: SCRIPTS,
+    field: 0,
+    default: "Unknown",
+    aliases: &[],
+};
+
+/// Maps short script aliases (`Latn`) to their long names (`Latin`), as used by
+/// `ScriptExtensions.txt` and `Scripts.txt` respectively.
+fn script_aliases() -> HashMap<&'static str, &'static str> {
+    PROPERTY_VALUE_ALIASES
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .filter_map(|line| {
+            let mut fields = line.split(';').map(str::trim);
+            if fields.next()? != "sc" {
+                return None;
+            }
+            Some((fields.next()?, fields.next()?))
+        })
+        .collect()
+}
+
+impl UnipropsBuilder<'_> {
+    /// Returns the long names of the scripts which have a variant in the generated `Script` enum.
+    pub(crate) fn script_names(&self, ages: Option<&Ages>) -> BTreeSet<String> {
+        let groups = ucd::to_groups_with_defaults(SCRIPTS, 0);
+        let mut names = self
+            .restrict_to_assigned(ages, groups)
+            .into_iter()
+            .map(|g| g.value)
+            .collect::<BTreeSet<_>>();
+        names.insert(SCRIPT.default.to_string());
+        names
+    }
+
+    pub(crate) fn generate_scripts(&self, ages: Option<&Ages>) -> TokenStream {
+        let script = self.generate_enumerated(&SCRIPT, ages);
+
+        let aliases = script_aliases();
+        let names = self.script_names(ages);
+        let mut groups = ucd::to_groups(&ucd::parse_property_file(SCRIPT_EXTENSIONS), 0);
+        for group in &mut groups {
+            // Scripts newer than `assigned_since` have no variant, so they are dropped
+            group.value = group
+                .value
+                .split_whitespace()
+                .map(|alias| *aliases.get(alias).expect("Unknown script alias"))
+                .filter(|name| names.contains(*name))
+                .collect::<Vec<_>>()
+                .join(" ");
+        }
+        groups.retain(|g| !g.value.is_empty());
+        let groups = self.restrict_to_assigned(ages, groups);
+
+        let table = PropertyTable {
+            name: "ScriptExtensions",
+            value_type: quote! { &'static [Script] },
+            value_size: slice_size(),
+            option_size: slice_size(),
+            groups: &groups,
+            value_tokens: &|value| {
+                let variants = value.split_whitespace().map(variant_ident);
+                quote! { &[#(Script::#variants),*] }
+            },
+        };
+        let (statics, body) = table.generate(self.table_options());
+
+        let variants = names.iter().map(|name| variant_ident(name));
+        let variants_len = names.len();
+
+        quote! {
+            #script
+
+            #statics
+
+            static SCRIPT_VARIANTS: [Script; #variants_len] = [ #(Script::#variants),* ];
+
+            /// Returns the `Script_Extensions` of `c`: the scripts `c` is used with. For most
+            /// characters this is just `Script::from_char(c)`.
+            #[inline(always)]
+            pub fn script_extensions(c: char) -> &'static [Script] {
+                let extensions: ::std::option::Option<&'static [Script]> = { #body };
+                extensions.unwrap_or_else(|| {
+                    let script = Script::from_char(c);
+                    // SAFETY: `SCRIPT_VARIANTS` lists every variant in declaration order
+                    ::std::slice::from_ref(unsafe { SCRIPT_VARIANTS.get_unchecked(script as usize) })
+                })
+            }
+        }
+    }
+}
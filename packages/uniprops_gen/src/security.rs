@@ -0,0 +1,223 @@
+//! UTS #39 identifier profile (`IdentifierStatus.txt`, `IdentifierType.txt`) and restriction
+//! level detection on top of `Script_Extensions`.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{
+    Ages, UnipropsBuilder,
+    enumerated::{EnumeratedProperty, variant_ident},
+    lookup::{PropertyTable, slice_size},
+    ucd,
+};
+
+pub(crate) const IDENTIFIER_STATUS: &str = include_str!("../assets/IdentifierStatus.txt");
+pub(crate) const IDENTIFIER_TYPE: &str = include_str!("../assets/IdentifierType.txt");
+
+/// Every `Identifier_Type` value, from most to least restrictive.
+const IDENTIFIER_TYPES: &[&str] = &[
+    "Not_Character",
+    "Deprecated",
+    "Default_Ignorable",
+    "Not_NFKC",
+    "Not_XID",
+    "Exclusion",
+    "Obsolete",
+    "Technical",
+    "Uncommon_Use",
+    "Limited_Use",
+    "Inclusion",
+    "Recommended",
+];
+
+/// Scripts listed in UAX #31 Table 5, "Recommended Scripts".
+const RECOMMENDED_SCRIPTS: &[&str] = &[
+    "Arabic",
+    "Armenian",
+    "Bengali",
+    "Bopomofo",
+    "Cyrillic",
+    "Devanagari",
+    "Ethiopic",
+    "Georgian",
+    "Greek",
+    "Gujarati",
+    "Gurmukhi",
+    "Han",
+    "Hangul",
+    "Hebrew",
+    "Hiragana",
+    "Kannada",
+    "Katakana",
+    "Khmer",
+    "Lao",
+    "Latin",
+    "Malayalam",
+    "Myanmar",
+    "Oriya",
+    "Sinhala",
+    "Tamil",
+    "Telugu",
+    "Thaana",
+    "Thai",
+    "Tibetan",
+];
+
+impl UnipropsBuilder<'_> {
+    /// Generates the identifier profile and `restriction_level`. Requires the output of
+    /// [`Self::generate_scripts`] in the same module.
+    pub(crate) fn generate_identifier_security(&self, ages: Option<&Ages>) -> TokenStream {
+        let status = self.generate_enumerated(
+            &EnumeratedProperty {
+                name: "IdentifierStatus",
+                source: IDENTIFIER_STATUS,
+                field: 0,
+                default: "Restricted",
+                aliases: &[],
+            },
+            ages,
+        );
+
+        let groups = ucd::to_groups(&ucd::parse_property_file(IDENTIFIER_TYPE), 0);
+        let groups = self.restrict_to_assigned(ages, groups);
+        let table = PropertyTable {
+            name: "IdentifierType",
+            value_type: quote! { &'static [IdentifierType] },
+            value_size: slice_size(),
+            option_size: slice_size(),
+            groups: &groups,
+            value_tokens: &|value| {
+                let variants = value.split_whitespace().map(variant_ident);
+                quote! { &[#(IdentifierType::#variants),*] }
+            },
+        };
+        let (statics, body) = table.generate(self.table_options());
+        let types = IDENTIFIER_TYPES.iter().map(|t| variant_ident(t));
+
+        // Scripts newer than `assigned_since` have no `Script` variant
+        let scripts = self.script_names(ages);
+        let recommended = RECOMMENDED_SCRIPTS
+            .iter()
+            .filter(|name| scripts.contains(**name))
+            .map(|name| variant_ident(name));
+
+        quote! {
+            #status
+
+            /// Returns the UTS #39 `Identifier_Status` of `c`.
+            #[inline(always)]
+            pub fn identifier_status(c: char) -> IdentifierStatus {
+                IdentifierStatus::from_char(c)
+            }
+
+            /// A UTS #39 `Identifier_Type` value.
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            pub enum IdentifierType {
+                #(#types),*
+            }
+
+            #statics
+
+            /// Returns the UTS #39 `Identifier_Type` values of `c`, e.g.
+            /// `[Technical, Obsolete]`.
+            #[inline(always)]
+            pub fn identifier_type(c: char) -> &'static [IdentifierType] {
+                let types: ::std::option::Option<&'static [IdentifierType]> = { #body };
+                types.unwrap_or(&[IdentifierType::NotCharacter])
+            }
+
+            /// UTS #39 restriction levels, from most to least restrictive.
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+            pub enum RestrictionLevel {
+                /// Only characters of the identifier profile up to U+007E.
+                AsciiOnly,
+                /// All characters are allowed and belong to a single script.
+                SingleScript,
+                /// Single script, or Latin combined with Han + Hiragana + Katakana,
+                /// Han + Bopomofo or Han + Hangul.
+                HighlyRestrictive,
+                /// Highly restrictive, or Latin combined with one other recommended script
+                /// except Cyrillic and Greek.
+                ModeratelyRestrictive,
+                /// All characters are allowed, in any combination of scripts.
+                MinimallyRestrictive,
+                /// Contains characters outside of the identifier profile.
+                Unrestricted,
+            }
+
+            fn is_recommended_script(script: Script) -> bool {
+                matches!(script, #(Script::#recommended)|*)
+            }
+
+            /// `Common` and `Inherited` characters are used with every script.
+            fn is_script_neutral(scripts: &[Script]) -> bool {
+                matches!(scripts, [Script::Common] | [Script::Inherited])
+            }
+
+            /// Returns `true` if every character of `s` is used with at least one of `scripts`.
+            fn is_covered_by(s: &str, scripts: &[Script]) -> bool {
+                s.chars().all(|c| {
+                    let extensions = script_extensions(c);
+                    is_script_neutral(extensions) || extensions.iter().any(|script| scripts.contains(script))
+                })
+            }
+
+            /// Returns `true` if the resolved script set of `s` is not empty, treating Han with
+            /// Hiragana and Katakana (Japanese), Bopomofo (Chinese) or Hangul (Korean) as one
+            /// script.
+            fn is_single_script(s: &str) -> bool {
+                let first = s
+                    .chars()
+                    .map(script_extensions)
+                    .find(|extensions| !is_script_neutral(extensions));
+                let ::std::option::Option::Some(first) = first else {
+                    return true;
+                };
+
+                first.iter().any(|&script| is_covered_by(s, &[script]))
+                    || is_covered_by(s, &[Script::Han, Script::Hiragana, Script::Katakana])
+                    || is_covered_by(s, &[Script::Han, Script::Bopomofo])
+                    || is_covered_by(s, &[Script::Han, Script::Hangul])
+            }
+
+            fn is_highly_restrictive(s: &str) -> bool {
+                is_covered_by(s, &[Script::Latin, Script::Han, Script::Hiragana, Script::Katakana])
+                    || is_covered_by(s, &[Script::Latin, Script::Han, Script::Bopomofo])
+                    || is_covered_by(s, &[Script::Latin, Script::Han, Script::Hangul])
+            }
+
+            fn is_moderately_restrictive(s: &str) -> bool {
+                let first = s.chars().map(script_extensions).find(|extensions| {
+                    !is_script_neutral(extensions) && !extensions.contains(&Script::Latin)
+                });
+                let ::std::option::Option::Some(first) = first else {
+                    return true;
+                };
+
+                first.iter().any(|&script| {
+                    is_recommended_script(script)
+                        && script != Script::Cyrillic
+                        && script != Script::Greek
+                        && is_covered_by(s, &[Script::Latin, script])
+                })
+            }
+
+            /// Returns the UTS #39 restriction level of the identifier `s`.
+            pub fn restriction_level(s: &str) -> RestrictionLevel {
+                if !s.chars().all(|c| identifier_status(c) == IdentifierStatus::Allowed) {
+                    RestrictionLevel::Unrestricted
+                } else if s.chars().all(|c| c <= '\u{7E}') {
+                    RestrictionLevel::AsciiOnly
+                } else if is_single_script(s) {
+                    RestrictionLevel::SingleScript
+                } else if is_highly_restrictive(s) {
+                    RestrictionLevel::HighlyRestrictive
+                } else if is_moderately_restrictive(s) {
+                    RestrictionLevel::ModeratelyRestrictive
+                } else {
+                    RestrictionLevel::MinimallyRestrictive
+                }
+            }
+        }
+    }
+}
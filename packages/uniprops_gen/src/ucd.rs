@@ -0,0 +1,131 @@
+//! Parsing helpers for the semicolon-separated property files of the Unicode Character Database
+//! (`DerivedAge.txt`, `IndicSyllabicCategory.txt`, ...).
+
+use crate::MappingGroup;
+
+/// A single data line of a UCD property file: a codepoint range and its remaining fields.
+#[derive(Debug, Clone)]
+pub(crate) struct PropertyEntry<'s> {
+    pub start: u32,
+    pub end: u32,
+    pub fields: Vec<&'s str>,
+}
+
+pub(crate) fn parse_code_point(s: &str) -> u32 {
+    u32::from_str_radix(s.trim(), 16).expect("Invalid code point in UCD file")
+}
+
+/// Parses either a single code point (`0041`) or a range (`0041..005A`).
+pub(crate) fn parse_range(s: &str) -> (u32, u32) {
+    match s.trim().split_once("..") {
+        Some((start, end)) => (parse_code_point(start), parse_code_point(end)),
+        None => {
+            let cp = parse_code_point(s);
+            (cp, cp)
+        }
+    }
+}
+
+fn parse_line(line: &str) -> Option<PropertyEntry<'_>> {
+    let data = line.split('#').next().unwrap_or("").trim();
+    if data.is_empty() {
+        return None;
+    }
+
+    let mut fields = data.split(';').map(str::trim);
+    let (start, end) = parse_range(fields.next()?);
+
+    Some(PropertyEntry {
+        start,
+        end,
+        fields: fields.collect(),
+    })
+}
+
+/// Returns the Unicode version in the first line of a UCD file, e.g. `14.0.0` for
+/// `# DerivedAge-14.0.0.txt`, or `None` for files without a versioned name.
+pub(crate) fn file_version(src: &str) -> Option<&str> {
+    let name = src.lines().next()?.trim_start_matches('#').trim();
+    let (_, version) = name.strip_suffix(".txt")?.rsplit_once('-')?;
+    version
+        .starts_with(|c: char| c.is_ascii_digit())
+        .then_some(version)
+}
+
+/// Parses all data lines of a UCD property file, skipping comments and blank lines.
+pub(crate) fn parse_property_file(src: &str) -> Vec<PropertyEntry<'_>> {
+    src.lines().filter_map(parse_line).collect()
+}
+
+/// Parses the `# @missing:` lines which declare default values for unlisted codepoints.
+///
+/// The lines are returned in file order; later lines take precedence over earlier ones.
+pub(crate) fn parse_missing(src: &str) -> Vec<PropertyEntry<'_>> {
+    src.lines()
+        .filter_map(|line| line.strip_prefix("# @missing:"))
+        .filter_map(parse_line)
+        .collect()
+}
+
+/// Converts entries into sorted [`MappingGroup`]s, using `field` as the value and merging
+/// adjacent ranges with equal values.
+pub(crate) fn to_groups(entries: &[PropertyEntry], field: usize) -> Vec<MappingGroup> {
+    let mut sorted = entries.iter().collect::<Vec<_>>();
+    sorted.sort_by_key(|e| e.start);
+
+    let mut groups: Vec<MappingGroup> = Vec::new();
+    for entry in sorted {
+        let value = entry.fields[field];
+        if let Some(last) = groups.last_mut() {
+            if last.end + 1 == entry.start && last.value == value {
+                last.end = entry.end;
+                continue;
+            }
+        }
+        groups.push(MappingGroup {
+            value: value.to_string(),
+            start: entry.start,
+            end: entry.end,
+        });
+    }
+    groups
+}
+
+/// Same as [`to_groups`], but codepoints missing from the file get the value of the last
+/// `@missing` line covering them, so the groups span the whole codepoint range.
+pub(crate) fn to_groups_with_defaults(src: &str, field: usize) -> Vec<MappingGroup> {
+    let mut values: Vec<Option<&str>> = vec![None; 0x110000];
+    let explicit = parse_property_file(src);
+    for entry in parse_missing(src).iter().chain(&explicit) {
+        for cp in entry.start..=entry.end {
+            values[cp as usize] = Some(entry.fields[field]);
+        }
+    }
+
+    let mut groups: Vec<MappingGroup> = Vec::new();
+    for (cp, value) in (0..).zip(values) {
+        let Some(value) = value else { continue };
+        if let Some(last) = groups.last_mut() {
+            if last.end + 1 == cp && last.value == value {
+                last.end = cp;
+                continue;
+            }
+        }
+        groups.push(MappingGroup {
+            value: value.to_string(),
+            start: cp,
+            end: cp,
+        });
+    }
+    groups
+}
+
+/// Collects the ranges listed for a binary `property` in a multi-property file such as
+/// `DerivedCoreProperties.txt`.
+pub(crate) fn binary_property_groups(src: &str, property: &str) -> Vec<MappingGroup> {
+    let entries = parse_property_file(src)
+        .into_iter()
+        .filter(|e| e.fields[0] == property)
+        .collect::<Vec<_>>();
+    to_groups(&entries, 0)
+}
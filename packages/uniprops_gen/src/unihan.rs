@@ -0,0 +1,261 @@
+//! Support for the Unihan database (`Unihan_IRGSources.txt`, `Unihan_NumericValues.txt` and
+//! `Unihan_Readings.txt`).
+//!
+//! Unihan is far too large to be bundled with the crate, so the files are read from the
+//! directory passed to [`UnipropsBuilder::with_unihan`].
+
+use std::{fs, path::Path};
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use crate::{Ages, MappingGroup, UnipropsBuilder, lookup::PropertyTable};
+
+/// A single `U+XXXX<TAB>kField<TAB>value` line.
+struct UnihanEntry<'s> {
+    code_point: u32,
+    field: &'s str,
+    value: &'s str,
+}
+
+fn read_unihan_file(dir: &Path, name: &str) -> String {
+    let path = dir.join(name);
+    fs::read_to_string(&path).unwrap_or_else(|e| panic!("Failed to read {}: {e}", path.display()))
+}
+
+fn parse_unihan(src: &str) -> Vec<UnihanEntry<'_>> {
+    src.lines()
+        .filter(|line| !line.starts_with('#') && !line.trim().is_empty())
+        .map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            let code_point = fields
+                .next()
+                .and_then(|cp| cp.strip_prefix("U+"))
+                .map(|cp| u32::from_str_radix(cp, 16).expect("Invalid code point in Unihan"))
+                .expect("Missing code point in Unihan");
+            UnihanEntry {
+                code_point,
+                field: fields.next().expect("Missing field name in Unihan"),
+                value: fields.next().expect("Missing field value in Unihan").trim(),
+            }
+        })
+        .collect()
+}
+
+/// Collects the first value of `field` for every codepoint into sorted, merged groups.
+fn field_groups(entries: &[UnihanEntry], field: &str) -> Vec<MappingGroup> {
+    let mut values = entries
+        .iter()
+        .filter(|e| e.field == field)
+        .map(|e| {
+            let first = e.value.split(' ').next().unwrap_or(e.value);
+            (e.code_point, first)
+        })
+        .collect::<Vec<_>>();
+    values.sort_by_key(|&(cp, _)| cp);
+
+    let mut groups: Vec<MappingGroup> = Vec::new();
+    for (cp, value) in values {
+        if let Some(last) = groups.last_mut() {
+            if last.end + 1 == cp && last.value == value {
+                last.end = cp;
+                continue;
+            }
+        }
+        groups.push(MappingGroup {
+            value: value.to_string(),
+            start: cp,
+            end: cp,
+        });
+    }
+    groups
+}
+
+/// Parses a `kRSUnicode` value such as `169'.0` into `(radical, simplified, strokes)`.
+fn parse_radical_stroke(value: &str) -> (u8, bool, i8) {
+    let (radical, strokes) = value.split_once('.').expect("Invalid kRSUnicode value");
+    let simplified = radical.ends_with('\'');
+    (
+        radical
+            .trim_end_matches('\'')
+            .parse()
+            .expect("Invalid kRSUnicode radical"),
+        simplified,
+        strokes.parse().expect("Invalid kRSUnicode strokes"),
+    )
+}
+
+impl UnipropsBuilder<'_> {
+    pub(crate) fn generate_unihan(&self, dir: &Path, ages: Option<&Ages>) -> TokenStream {
+        let irg_sources = read_unihan_file(dir, "Unihan_IRGSources.txt");
+        let numeric_values = read_unihan_file(dir, "Unihan_NumericValues.txt");
+        let readings = read_unihan_file(dir, "Unihan_Readings.txt");
+
+        let irg_sources = parse_unihan(&irg_sources);
+        let numeric_values = parse_unihan(&numeric_values);
+        let readings = parse_unihan(&readings);
+
+        let rs_unicode = self.generate_unihan_field(
+            "KRsUnicode",
+            self.restrict_to_assigned(ages, field_groups(&irg_sources, "kRSUnicode")),
+            quote! { RadicalStroke },
+            3,
+            3,
+            &|value| {
+                let (radical, simplified, strokes) = parse_radical_stroke(value);
+                quote! { RadicalStroke { radical: #radical, simplified: #simplified, strokes: #strokes } }
+            },
+        );
+        let total_strokes = self.generate_unihan_field(
+            "KTotalStrokes",
+            self.restrict_to_assigned(ages, field_groups(&irg_sources, "kTotalStrokes")),
+            quote! { u8 },
+            1,
+            2,
+            &|value| {
+                let strokes: u8 = value.parse().expect("Invalid kTotalStrokes value");
+                quote! { #strokes }
+            },
+        );
+        let mandarin = self.generate_interned_unihan_field(
+            "KMandarin",
+            self.restrict_to_assigned(ages, field_groups(&readings, "kMandarin")),
+            quote! { &'static str },
+            &|value| quote! { #value },
+        );
+        let numeric_tokens = |value: &str| {
+            let value: u64 = value.parse().expect("Invalid numeric value in Unihan");
+            quote! { #value }
+        };
+        let primary_numeric = self.generate_interned_unihan_field(
+            "KPrimaryNumeric",
+            self.restrict_to_assigned(ages, field_groups(&numeric_values, "kPrimaryNumeric")),
+            quote! { u64 },
+            &numeric_tokens,
+        );
+        let accounting_numeric = self.generate_interned_unihan_field(
+            "KAccountingNumeric",
+            self.restrict_to_assigned(ages, field_groups(&numeric_values, "kAccountingNumeric")),
+            quote! { u64 },
+            &numeric_tokens,
+        );
+        let other_numeric = self.generate_interned_unihan_field(
+            "KOtherNumeric",
+            self.restrict_to_assigned(ages, field_groups(&numeric_values, "kOtherNumeric")),
+            quote! { u64 },
+            &numeric_tokens,
+        );
+
+        quote! {
+            /// A radical-stroke count from the `kRSUnicode` Unihan field.
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+            pub struct RadicalStroke {
+                /// Number of the KangXi radical (1-214).
+                pub radical: u8,
+                /// Whether the character uses the simplified form of the radical.
+                pub simplified: bool,
+                /// Number of residual strokes besides the radical.
+                pub strokes: i8,
+            }
+
+            #rs_unicode
+            #total_strokes
+            #mandarin
+            #primary_numeric
+            #accounting_numeric
+            #other_numeric
+        }
+    }
+
+    /// Generates `fn k_field(c: char) -> Option<value_type>` over a table of values, where
+    /// `value_size` is the size of `value_type` and `option_size` the size of
+    /// `Option<value_type>`.
+    fn generate_unihan_field(
+        &self,
+        name: &str,
+        groups: Vec<MappingGroup>,
+        value_type: TokenStream,
+        value_size: usize,
+        option_size: usize,
+        value_tokens: &dyn Fn(&str) -> TokenStream,
+    ) -> TokenStream {
+        let table = PropertyTable {
+            name,
+            value_type: value_type.clone(),
+            value_size,
+            option_size,
+            groups: &groups,
+            value_tokens,
+        };
+        let (statics, body) = table.generate(self.table_options());
+        let fn_ident = unihan_fn_ident(name);
+
+        quote! {
+            #statics
+
+            #[inline(always)]
+            pub fn #fn_ident(c: char) -> ::std::option::Option<#value_type> {
+                #body
+            }
+        }
+    }
+
+    /// Same as [`Self::generate_unihan_field`], but stores `u16` indices into a table of distinct
+    /// values. Used for wide values (strings, `u64`) which would bloat the lookup tables.
+    fn generate_interned_unihan_field(
+        &self,
+        name: &str,
+        mut groups: Vec<MappingGroup>,
+        value_type: TokenStream,
+        value_tokens: &dyn Fn(&str) -> TokenStream,
+    ) -> TokenStream {
+        let mut values = groups.iter().map(|g| g.value.clone()).collect::<Vec<_>>();
+        values.sort();
+        values.dedup();
+        for group in &mut groups {
+            group.value = values.binary_search(&group.value).unwrap().to_string();
+        }
+
+        let table = PropertyTable {
+            name,
+            value_type: quote! { u16 },
+            value_size: 2,
+            option_size: 4,
+            groups: &groups,
+            value_tokens: &|index| {
+                let index: u16 = index.parse().unwrap();
+                quote! { #index }
+            },
+        };
+        let (statics, body) = table.generate(self.table_options());
+        let fn_ident = unihan_fn_ident(name);
+        let values_ident = format_ident!("{}_VALUES", fn_ident.to_string().to_uppercase());
+        let values_len = values.len();
+        let values = values.iter().map(|value| value_tokens(value));
+
+        quote! {
+            #statics
+
+            static #values_ident: [#value_type; #values_len] = [ #(#values),* ];
+
+            #[inline(always)]
+            pub fn #fn_ident(c: char) -> ::std::option::Option<#value_type> {
+                let index: ::std::option::Option<u16> = { #body };
+                // SAFETY: Indices are generated from the positions in the values array
+                index.map(|i| unsafe { *#values_ident.get_unchecked(i as usize) })
+            }
+        }
+    }
+}
+
+/// `KTotalStrokes` -> `k_total_strokes`
+fn unihan_fn_ident(name: &str) -> proc_macro2::Ident {
+    let mut ident = String::new();
+    for (i, ch) in name.chars().enumerate() {
+        if ch.is_uppercase() && i > 0 {
+            ident.push('_');
+        }
+        ident.push(ch.to_ascii_lowercase());
+    }
+    format_ident!("{}", ident)
+}
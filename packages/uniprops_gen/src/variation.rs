@@ -0,0 +1,71 @@
+//! Support for variation sequences from `StandardizedVariants.txt` and
+//! `emoji-variation-sequences.txt`.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{Ages, UnipropsBuilder, ucd};
+
+const STANDARDIZED_VARIANTS: &str = include_str!("../assets/StandardizedVariants.txt");
+pub(crate) const EMOJI_VARIATION_SEQUENCES: &str =
+    include_str!("../assets/emoji-variation-sequences.txt");
+
+/// Parses `base selector; description; ...` lines into `(base, selector, description)`.
+fn parse_sequences(src: &str) -> Vec<(u32, u32, &str)> {
+    src.lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let mut fields = line.split(';').map(str::trim);
+            let mut sequence = fields
+                .next()
+                .expect("Missing variation sequence")
+                .split_whitespace()
+                .map(ucd::parse_code_point);
+            let base = sequence.next().expect("Missing base character");
+            let selector = sequence.next().expect("Missing variation selector");
+            let description = fields.next().expect("Missing variation description");
+            (base, selector, description)
+        })
+        .collect()
+}
+
+impl UnipropsBuilder<'_> {
+    pub(crate) fn generate_variation_sequences(&self, ages: Option<&Ages>) -> TokenStream {
+        let mut sequences = parse_sequences(STANDARDIZED_VARIANTS);
+        sequences.extend(parse_sequences(EMOJI_VARIATION_SEQUENCES));
+        sequences.retain(|&(base, _, _)| self.is_assigned(ages, base));
+        sequences.sort();
+        sequences.dedup_by_key(|&mut (base, selector, _)| (base, selector));
+
+        let len = sequences.len();
+        let sequences = sequences.iter().map(|&(base, selector, description)| {
+            let base = char::from_u32(base).unwrap();
+            let selector = char::from_u32(selector).unwrap();
+            quote! { (#base, #selector, #description) }
+        });
+
+        quote! {
+            static VARIATION_SEQUENCES: [(char, char, &str); #len] = [
+                #(#sequences),*
+            ];
+
+            /// Returns the description of the variation sequence `base` + `selector`, e.g.
+            /// `"emoji style"`, or `None` if the sequence is not standardized.
+            pub fn variation_description(base: char, selector: char) -> ::std::option::Option<&'static str> {
+                VARIATION_SEQUENCES
+                    .binary_search_by(|&(b, s, _)| (b, s).cmp(&(base, selector)))
+                    .ok()
+                    // SAFETY: We found an element with index i just now, it MUST be in array
+                    .map(|i| unsafe { VARIATION_SEQUENCES.get_unchecked(i) }.2)
+            }
+
+            /// Returns `true` if `base` followed by `selector` is a standardized or emoji
+            /// variation sequence.
+            #[inline]
+            pub fn is_valid_variation_sequence(base: char, selector: char) -> bool {
+                variation_description(base, selector).is_some()
+            }
+        }
+    }
+}
@@ -0,0 +1,22 @@
+//! Support for the `Vertical_Orientation` property from `VerticalOrientation.txt`.
+
+use proc_macro2::TokenStream;
+
+use crate::{Ages, UnipropsBuilder, enumerated::EnumeratedProperty};
+
+pub(crate) const VERTICAL_ORIENTATION: &str = include_str!("../assets/VerticalOrientation.txt");
+
+impl UnipropsBuilder<'_> {
+    pub(crate) fn generate_vertical_orientation(&self, ages: Option<&Ages>) -> TokenStream {
+        self.generate_enumerated(
+            &EnumeratedProperty {
+                name: "VerticalOrientation",
+                source: VERTICAL_ORIENTATION,
+                field: 0,
+                default: "R",
+                aliases: &[],
+            },
+            ages,
+        )
+    }
+}
@@ -1,4 +1,24 @@
-curl -o "$0/packages/uniprops_gen/UnicodeData.txt" https://www.unicode.org/Public/UCD/latest/ucd/UnicodeData.txt
+curl -o "$0/packages/uniprops_gen/assets/UnicodeData.txt" https://www.unicode.org/Public/UCD/latest/ucd/UnicodeData.txt
+curl -o "$0/packages/uniprops_gen/assets/DerivedAge.txt" https://www.unicode.org/Public/UCD/latest/ucd/DerivedAge.txt
+curl -o "$0/packages/uniprops_gen/assets/IndicSyllabicCategory.txt" https://www.unicode.org/Public/UCD/latest/ucd/IndicSyllabicCategory.txt
+curl -o "$0/packages/uniprops_gen/assets/IndicPositionalCategory.txt" https://www.unicode.org/Public/UCD/latest/ucd/IndicPositionalCategory.txt
+curl -o "$0/packages/uniprops_gen/assets/ArabicShaping.txt" https://www.unicode.org/Public/UCD/latest/ucd/ArabicShaping.txt
+curl -o "$0/packages/uniprops_gen/assets/DerivedJoiningType.txt" https://www.unicode.org/Public/UCD/latest/ucd/extracted/DerivedJoiningType.txt
+curl -o "$0/packages/uniprops_gen/assets/VerticalOrientation.txt" https://www.unicode.org/Public/UCD/latest/ucd/VerticalOrientation.txt
+curl -o "$0/packages/uniprops_gen/assets/confusables.txt" https://www.unicode.org/Public/security/latest/confusables.txt
+curl -o "$0/packages/uniprops_gen/assets/IdentifierStatus.txt" https://www.unicode.org/Public/security/latest/IdentifierStatus.txt
+curl -o "$0/packages/uniprops_gen/assets/IdentifierType.txt" https://www.unicode.org/Public/security/latest/IdentifierType.txt
+curl -o "$0/packages/uniprops_gen/assets/Scripts.txt" https://www.unicode.org/Public/UCD/latest/ucd/Scripts.txt
+curl -o "$0/packages/uniprops_gen/assets/ScriptExtensions.txt" https://www.unicode.org/Public/UCD/latest/ucd/ScriptExtensions.txt
+curl -o "$0/packages/uniprops_gen/assets/PropertyValueAliases.txt" https://www.unicode.org/Public/UCD/latest/ucd/PropertyValueAliases.txt
+curl -o "$0/packages/uniprops_gen/assets/DerivedCoreProperties.txt" https://www.unicode.org/Public/UCD/latest/ucd/DerivedCoreProperties.txt
+curl -o "$0/packages/uniprops_gen/assets/allkeys.txt" https://www.unicode.org/Public/UCA/latest/allkeys.txt
+curl -o "$0/packages/uniprops_gen/assets/StandardizedVariants.txt" https://www.unicode.org/Public/UCD/latest/ucd/StandardizedVariants.txt
+curl -o "$0/packages/uniprops_gen/assets/emoji-variation-sequences.txt" https://www.unicode.org/Public/UCD/latest/ucd/emoji/emoji-variation-sequences.txt
+curl -o "$0/packages/uniprops_gen/assets/emoji-sequences.txt" https://www.unicode.org/Public/emoji/latest/emoji-sequences.txt
+curl -o "$0/packages/uniprops_gen/assets/emoji-zwj-sequences.txt" https://www.unicode.org/Public/emoji/latest/emoji-zwj-sequences.txt
+curl -o "$0/packages/uniprops_gen/assets/CaseFolding.txt" https://www.unicode.org/Public/UCD/latest/ucd/CaseFolding.txt
+curl -o "$0/packages/uniprops_gen/assets/DerivedNormalizationProps.txt" https://www.unicode.org/Public/UCD/latest/ucd/DerivedNormalizationProps.txt
 if git diff --quiet; then
     echo "No changes detected."
     echo "changes=false" >> $GITHUB_OUTPUT